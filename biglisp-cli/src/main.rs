@@ -1,7 +1,11 @@
+use biglisp::guts::{builtins, LispExpr};
+use biglisp::runtime::Interpreter;
 use clap::{Args, Parser, Subcommand};
 use std::fs;
+use std::hint::black_box;
 use std::io::{self, Write};
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 #[derive(Parser)]
 #[command(name = "biglisp")]
@@ -23,6 +27,8 @@ enum Commands {
     Examples,
     /// Validate biglisp syntax without execution
     Check(CheckArgs),
+    /// Time expansion to Rust and interpreted evaluation of a biglisp file
+    Bench(BenchArgs),
 }
 
 #[derive(Args)]
@@ -39,6 +45,9 @@ struct RunArgs {
     /// Show verbose output
     #[arg(short, long)]
     verbose: bool,
+    /// Evaluate the file and print how long each top-level form took
+    #[arg(long)]
+    profile: bool,
 }
 
 #[derive(Args)]
@@ -47,6 +56,15 @@ struct CheckArgs {
     file: PathBuf,
 }
 
+#[derive(Args)]
+struct BenchArgs {
+    /// The biglisp file to benchmark
+    file: PathBuf,
+    /// How many times to expand and evaluate the file
+    #[arg(short = 'n', long, default_value_t = 100)]
+    iterations: usize,
+}
+
 fn main() {
     let cli = Cli::parse();
 
@@ -55,6 +73,7 @@ fn main() {
         Some(Commands::Run(args)) => run_file(args),
         Some(Commands::Examples) => show_examples(),
         Some(Commands::Check(args)) => check_file(args),
+        Some(Commands::Bench(args)) => bench_file(args),
         None => run_repl(ReplArgs { verbose: false }),
     }
 }
@@ -316,6 +335,15 @@ fn run_file(args: RunArgs) {
                 println!("🔄 Executing...\n");
             }
 
+            if args.profile {
+                println!("🚀 Profiling biglisp file: {}", args.file.display());
+                if let Err(error) = profile_source(&content, &mut io::stdout()) {
+                    eprintln!("❌ {}", error);
+                    std::process::exit(1);
+                }
+                return;
+            }
+
             // In a real implementation, this would parse and execute the file
             println!("🚀 Executing biglisp file: {}", args.file.display());
             println!("📝 File contains {} lines", content.lines().count());
@@ -345,6 +373,57 @@ fn run_file(args: RunArgs) {
     }
 }
 
+/// Evaluates each top-level form of `src` in one interpreter, writing a
+/// line with its duration and value, followed by the total time.
+fn profile_source(src: &str, out: &mut impl Write) -> Result<(), String> {
+    let forms = LispExpr::parse_program(src).map_err(|error| format!("Parse error: {}", error))?;
+    let write_error = |error: io::Error| error.to_string();
+
+    let mut interpreter = Interpreter::new();
+    let mut total = Duration::ZERO;
+    for (i, form) in forms.iter().enumerate() {
+        let start = Instant::now();
+        let result = interpreter.eval(form);
+        let elapsed = start.elapsed();
+        total += elapsed;
+        match result {
+            Ok(value) => writeln!(
+                out,
+                "  ⏱️  form {} {}: {:?} => {}",
+                i + 1,
+                form_label(form),
+                elapsed,
+                value
+            )
+            .map_err(write_error)?,
+            Err(error) => {
+                return Err(format!(
+                    "Form {} {} failed: {}",
+                    i + 1,
+                    form_label(form),
+                    error
+                ))
+            }
+        }
+    }
+    writeln!(out, "  📊 Total: {:?} over {} forms", total, forms.len()).map_err(write_error)
+}
+
+/// A short description of a top-level form for profiling output, such as
+/// `(defn ...)`.
+fn form_label(form: &LispExpr) -> String {
+    match form {
+        LispExpr::List(items) => match items.first() {
+            Some(LispExpr::Symbol(head)) => format!("({} ...)", head),
+            Some(LispExpr::Operator(head)) => format!("({} ...)", head),
+            _ => "(...)".to_string(),
+        },
+        LispExpr::Vector(_) => "[...]".to_string(),
+        LispExpr::Symbol(name) => name.to_string(),
+        _ => "literal".to_string(),
+    }
+}
+
 fn check_file(args: CheckArgs) {
     if !args.file.exists() {
         eprintln!("❌ Error: File '{}' not found", args.file.display());
@@ -404,53 +483,92 @@ fn check_file(args: CheckArgs) {
     }
 }
 
-fn show_examples() {
-    println!("🎯 BigLisp Syntax Examples:");
-    println!();
+fn bench_file(args: BenchArgs) {
+    let content = match fs::read_to_string(&args.file) {
+        Ok(content) => content,
+        Err(error) => {
+            eprintln!("❌ Error reading file '{}': {}", args.file.display(), error);
+            std::process::exit(1);
+        }
+    };
 
-    println!("📊 Basic Arithmetic:");
-    println!("  (+ 1 2 3)           ; Addition: 6");
-    println!("  (- 10 3 2)          ; Subtraction: 5");
-    println!("  (* 2 3 4)           ; Multiplication: 24");
-    println!("  (/ 12 3)            ; Division: 4");
-    println!();
+    match bench_source(&content, args.iterations) {
+        Ok(report) => {
+            println!("⏱️  Benchmarking: {}", args.file.display());
+            print!("{}", report);
+        }
+        Err(error) => {
+            eprintln!("❌ {}", error);
+            std::process::exit(1);
+        }
+    }
+}
 
-    println!("🔍 Comparisons:");
-    println!("  (= 5 5)             ; Equality: true");
-    println!("  (< 3 7)             ; Less than: true");
-    println!("  (> 7 3)             ; Greater than: true");
-    println!("  (gte 5 5)           ; Greater than or equal: true");
-    println!("  (lte 3 7)           ; Less than or equal: true");
-    println!("  (ne 3 7)            ; Not equal: true");
-    println!();
+/// Timings collected by `bench_source`.
+struct BenchReport {
+    forms: usize,
+    expand: Vec<Duration>,
+    /// Evaluation timings, or the interpreter's error if the program cannot
+    /// be evaluated (the interpreter supports fewer forms than `to_rust`).
+    eval: Result<Vec<Duration>, String>,
+}
 
-    println!("🧠 Boolean Logic:");
-    println!("  (and true false)    ; Logical AND: false");
-    println!("  (or false true)     ; Logical OR: true");
-    println!("  (not false)         ; Logical NOT: true");
-    println!();
+impl std::fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "  Forms:      {}", self.forms)?;
+        writeln!(f, "  Iterations: {}", self.expand.len())?;
+        writeln!(f, "  to_rust:    {}", timing_summary(&self.expand))?;
+        match &self.eval {
+            Ok(eval) => writeln!(f, "  eval:       {}", timing_summary(eval)),
+            Err(error) => writeln!(f, "  eval:       skipped ({})", error),
+        }
+    }
+}
 
-    println!("🎛️  Control Flow:");
-    println!("  (if (> 5 3) \"yes\" \"no\")              ; Conditional");
-    println!("  (let [x 5 y 10] (+ x y))              ; Local bindings: 15");
-    println!();
+/// Parses `src` once, then times `iterations` rounds of expansion with
+/// `to_rust` and of evaluation in a fresh interpreter.
+fn bench_source(src: &str, iterations: usize) -> Result<BenchReport, String> {
+    let forms = LispExpr::parse_program(src).map_err(|error| format!("Parse error: {}", error))?;
+    if iterations == 0 {
+        return Err("Iterations must be at least 1".to_string());
+    }
 
-    println!("📝 Strings:");
-    println!("  (str \"hello\" \" \" \"world\")          ; Concatenation");
-    println!("  (str \"Answer: \" 42)                 ; Mixed types");
-    println!();
+    let expand = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(LispExpr::to_rust_block(&forms));
+            start.elapsed()
+        })
+        .collect();
+
+    let eval = (0..iterations)
+        .map(|_| {
+            let mut interpreter = Interpreter::new();
+            let start = Instant::now();
+            for form in &forms {
+                black_box(interpreter.eval(form).map_err(|error| error.to_string())?);
+            }
+            Ok(start.elapsed())
+        })
+        .collect();
+
+    Ok(BenchReport {
+        forms: forms.len(),
+        expand,
+        eval,
+    })
+}
 
-    println!("📋 Lists/Vectors:");
-    println!("  [1 2 3 4]           ; Vector literal");
-    println!("  (first [1 2 3])     ; First element: 1");
-    println!("  (rest [1 2 3])      ; Rest: [2, 3]");
-    println!("  (count [1 2 3 4])   ; Count: 4");
-    println!("  (cons 0 [1 2 3])    ; Prepend: [0, 1, 2, 3]");
-    println!();
+/// Formats the min, mean and max of a non-empty set of timings.
+fn timing_summary(timings: &[Duration]) -> String {
+    let min = timings.iter().min().copied().unwrap_or_default();
+    let max = timings.iter().max().copied().unwrap_or_default();
+    let mean = timings.iter().sum::<Duration>() / timings.len().max(1) as u32;
+    format!("min {:?}, mean {:?}, max {:?}", min, mean, max)
+}
 
-    println!("🔧 Functions:");
-    println!("  (defn square [x] (* x x))             ; Define function");
-    println!("  (call square 5)                       ; Call function: 25");
+fn show_examples() {
+    println!("🎯 BigLisp Syntax Examples:");
     println!();
 
     println!("🏗️  Complex Examples:");
@@ -464,22 +582,71 @@ fn show_examples() {
     println!("  lisp!([x] (+ x 10))                  ; Captures Rust variable");
     println!();
 
-    println!("🔧 Math Utilities:");
-    println!("  (min 5 3 8)         ; Minimum value: 3");
-    println!("  (max 1 9 4)         ; Maximum value: 9");
-    println!("  (abs -7)            ; Absolute value: 7");
-    println!("  (modulo 10 3)       ; Modulo operation: 1");
-    println!("  (inc 5)             ; Increment: 6");
-    println!("  (dec 10)            ; Decrement: 9");
-    println!();
-
-    println!("🔍 Predicates:");
-    println!("  (zero 0)            ; Is zero: true");
-    println!("  (pos 5)             ; Is positive: true");
-    println!("  (neg -3)            ; Is negative: true");
-    println!("  (even 4)            ; Is even: true");
-    println!("  (odd 3)             ; Is odd: true");
+    print!("{}", builtins_help());
     println!();
 
     println!("🚀 Try these examples in the REPL!");
 }
+
+/// Builds the builtin reference from the registry, one line per form.
+fn builtins_help() -> String {
+    let mut help = String::from("📚 Builtins (name, arity, description):\n");
+    for builtin in builtins() {
+        help.push_str(&format!("  {}\n", builtin));
+    }
+    help
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bench_runs_small_program() {
+        let report = bench_source("(defn sq [x] (* x x)) (sq (+ 1 2 3))", 3).unwrap();
+        assert_eq!(report.forms, 2);
+        assert_eq!(report.expand.len(), 3);
+        assert_eq!(report.eval.as_ref().map(Vec::len), Ok(3));
+        assert!(report.to_string().contains("to_rust:"));
+
+        // Programs the interpreter cannot run still report expansion timings
+        let report = bench_source("(undefined-fn 1)", 2).unwrap();
+        assert_eq!(report.expand.len(), 2);
+        assert!(report.eval.is_err());
+
+        assert!(bench_source("(+ 1", 1).is_err());
+    }
+
+    #[test]
+    fn profile_prints_timing_per_form() {
+        let mut out = Vec::new();
+        profile_source("(defn sq [x] (* x x)) (sq 4) (+ 1 2)", &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 4, "{}", out);
+        assert!(lines[0].contains("form 1 (defn ...)"));
+        assert!(lines[1].contains("form 2 (sq ...)") && lines[1].ends_with("=> 16"));
+        assert!(lines[2].contains("form 3 (+ ...)") && lines[2].ends_with("=> 3"));
+        assert!(lines[3].contains("Total") && lines[3].contains("over 3 forms"));
+
+        let error = profile_source("(+ 1 2) (missing 1)", &mut Vec::new()).unwrap_err();
+        assert!(
+            error.starts_with("Form 2 (missing ...) failed"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn help_lists_every_builtin() {
+        let help = builtins_help();
+        for builtin in builtins() {
+            let line = format!("  {}", builtin);
+            assert!(
+                help.lines().any(|l| l == line),
+                "help is missing `{}`",
+                builtin.name
+            );
+        }
+    }
+}
@@ -0,0 +1,515 @@
+use std::fmt;
+
+/// How many arguments a builtin accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    /// Exactly this many arguments.
+    Exactly(usize),
+    /// This many arguments or more.
+    AtLeast(usize),
+    /// Between the two bounds, inclusive.
+    Range(usize, usize),
+}
+
+impl Arity {
+    /// Returns true if a call with `count` arguments is accepted.
+    pub fn accepts(&self, count: usize) -> bool {
+        match *self {
+            Arity::Exactly(n) => count == n,
+            Arity::AtLeast(n) => count >= n,
+            Arity::Range(min, max) => (min..=max).contains(&count),
+        }
+    }
+}
+
+impl fmt::Display for Arity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Arity::Exactly(n) => write!(f, "{}", n),
+            Arity::AtLeast(n) => write!(f, "{}+", n),
+            Arity::Range(min, max) => write!(f, "{}-{}", min, max),
+        }
+    }
+}
+
+/// Metadata describing one form understood by `expand_operation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Builtin {
+    /// The name the form is called by, e.g. `"+"` or `"contains?"`.
+    pub name: &'static str,
+    /// The number of arguments the form accepts.
+    pub arity: Arity,
+    /// A one-line summary of what the form does.
+    pub description: &'static str,
+}
+
+impl fmt::Display for Builtin {
+    /// Formats the builtin as a single help line: name, arity and description.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<14} {:<5} {}",
+            self.name,
+            self.arity.to_string(),
+            self.description
+        )
+    }
+}
+
+/// Returns metadata for every builtin form, in the order they are documented.
+///
+/// Tools such as the CLI use this to generate help text, so the list must be
+/// kept in step with the arms of `expand_operation`.
+///
+/// # Example
+/// ```rust
+/// let add = biglisp_core::builtins().iter().find(|b| b.name == "+").unwrap();
+/// assert!(add.arity.accepts(3));
+/// ```
+pub fn builtins() -> &'static [Builtin] {
+    BUILTINS
+}
+
+const fn builtin(name: &'static str, arity: Arity, description: &'static str) -> Builtin {
+    Builtin {
+        name,
+        arity,
+        description,
+    }
+}
+
+use Arity::{AtLeast, Exactly, Range};
+
+const BUILTINS: &[Builtin] = &[
+    // Arithmetic
+    builtin("+", AtLeast(0), "Sum of the arguments"),
+    builtin("-", AtLeast(1), "Negation, or left-to-right subtraction"),
+    builtin("*", AtLeast(0), "Product of the arguments"),
+    builtin("/", AtLeast(2), "Left-to-right division"),
+    builtin(
+        "%",
+        Exactly(2),
+        "Remainder of the first argument by the second",
+    ),
+    builtin("modulo", Exactly(2), "Alias for `%`"),
+    // Comparison
+    builtin("=", Exactly(2), "Equality"),
+    builtin("eq", Exactly(2), "Alias for `=`"),
+    builtin("<", Exactly(2), "Less than"),
+    builtin(">", Exactly(2), "Greater than"),
+    builtin("gte", Exactly(2), "Greater than or equal"),
+    builtin("lte", Exactly(2), "Less than or equal"),
+    builtin("ne", Exactly(2), "Inequality"),
+    builtin("not=", Exactly(2), "Alias for `ne`"),
+    // Control flow
+    builtin(
+        "if",
+        Range(2, 3),
+        "Conditional with an optional else branch",
+    ),
+    builtin(
+        "if-first",
+        Exactly(3),
+        "Branch on whether a collection has a first element",
+    ),
+    builtin(
+        "when-first",
+        Exactly(2),
+        "Run a body with the first element, if any",
+    ),
+    builtin("let", AtLeast(2), "Local bindings followed by a body"),
+    builtin("letfn", Exactly(2), "Mutually recursive local functions"),
+    builtin(
+        "do",
+        AtLeast(0),
+        "Evaluate forms in order, returning the last",
+    ),
+    builtin(
+        "def",
+        Exactly(2),
+        "Bind a name inside `do` or at the top level",
+    ),
+    builtin(
+        "with-vars",
+        AtLeast(2),
+        "Capture Rust variables for the body",
+    ),
+    builtin("while", Exactly(2), "Loop while a condition holds"),
+    builtin(
+        "while-collect",
+        Exactly(2),
+        "Loop, collecting each body value",
+    ),
+    builtin(
+        "dotimes",
+        Exactly(3),
+        "Run a body for each index below a count",
+    ),
+    builtin(
+        "dotimes-sum",
+        Exactly(3),
+        "Sum a body over each index below a count",
+    ),
+    builtin(
+        "doseq",
+        Exactly(2),
+        "Run a body for each element of a collection",
+    ),
+    builtin(
+        "doseq-indexed",
+        Exactly(2),
+        "Like `doseq`, also binding each index",
+    ),
+    builtin(
+        "for",
+        Exactly(2),
+        "Collect a body over a collection, with optional :when",
+    ),
+    builtin(
+        "case",
+        AtLeast(3),
+        "Dispatch on literal keys with an optional default",
+    ),
+    builtin(
+        "condp",
+        AtLeast(4),
+        "Dispatch on a predicate against each test value",
+    ),
+    builtin(
+        "cond->",
+        AtLeast(1),
+        "Thread a value through steps whose conditions hold",
+    ),
+    builtin(
+        "as->",
+        AtLeast(2),
+        "Thread a value through forms under a chosen name",
+    ),
+    builtin(
+        "some->",
+        AtLeast(1),
+        "Thread an Option through steps, stopping at None",
+    ),
+    builtin(
+        "doto",
+        AtLeast(1),
+        "Run side-effecting steps on a value and return it",
+    ),
+    // Functions
+    builtin("defn", AtLeast(3), "Define a function"),
+    builtin("fn", Exactly(2), "An anonymous function"),
+    builtin(
+        "closure",
+        Exactly(3),
+        "A closure capturing local bindings by value",
+    ),
+    builtin("call", AtLeast(1), "Call a function value"),
+    builtin("identity", Exactly(1), "Return the argument unchanged"),
+    builtin(
+        "constantly",
+        Exactly(1),
+        "A function that always returns the value",
+    ),
+    builtin(
+        "every-pred",
+        AtLeast(1),
+        "A predicate that holds when all of the given predicates do",
+    ),
+    builtin(
+        "any-pred",
+        AtLeast(1),
+        "A predicate that holds when any of the given predicates does",
+    ),
+    builtin(
+        "juxt",
+        AtLeast(1),
+        "A function returning a tuple of several functions' results",
+    ),
+    builtin(
+        "memoize",
+        Exactly(1),
+        "Cache a function's results by argument",
+    ),
+    // Errors
+    builtin(
+        "try",
+        AtLeast(1),
+        "Recover from a panic with catch/finally clauses",
+    ),
+    builtin(
+        "try-verbose",
+        AtLeast(1),
+        "Like `try`, but reports the panic",
+    ),
+    builtin("throw", Exactly(1), "Raise an error with a message"),
+    // Logic
+    builtin("and", AtLeast(0), "True if every argument is true"),
+    builtin("or", AtLeast(0), "True if any argument is true"),
+    builtin("xor", Exactly(2), "True if exactly one argument is true"),
+    builtin("nand", AtLeast(0), "False if every argument is true"),
+    builtin("nor", AtLeast(0), "True if no argument is true"),
+    builtin("and-val", AtLeast(1), "The first falsy value, or the last"),
+    builtin("or-val", AtLeast(1), "The first truthy value, or the last"),
+    builtin("not", Exactly(1), "Logical negation"),
+    // Collections
+    builtin("first", Exactly(1), "First element of a collection"),
+    builtin("rest", Exactly(1), "All but the first element"),
+    builtin("last", Exactly(1), "Last element of a collection"),
+    builtin("second", Exactly(1), "Second element of a collection"),
+    builtin("third", Exactly(1), "Third element of a collection"),
+    builtin("nthrest", Exactly(2), "All but the first `n` elements"),
+    builtin(
+        "take-nth",
+        Exactly(2),
+        "Every `n`th element of a collection",
+    ),
+    builtin(
+        "split-at",
+        Exactly(2),
+        "The first `n` elements and the rest, as a pair",
+    ),
+    builtin(
+        "slice",
+        Exactly(3),
+        "The elements between a start and end index",
+    ),
+    builtin("butlast", Exactly(1), "All but the last element"),
+    builtin("list", AtLeast(0), "Build a vector from the arguments"),
+    builtin(
+        "vec-of",
+        AtLeast(1),
+        "Build a vector with an explicit element type",
+    ),
+    builtin("cons", Exactly(2), "Prepend an element to a collection"),
+    builtin(
+        "char-at",
+        Exactly(2),
+        "The character at an index of a string",
+    ),
+    builtin("ascii-code", Exactly(1), "The character code of a char"),
+    builtin("code->char", Exactly(1), "The char for a character code"),
+    builtin(
+        "contains?",
+        Exactly(2),
+        "Whether a collection holds a value",
+    ),
+    builtin("has-key?", Exactly(2), "Whether a map holds a key"),
+    builtin("select-keys", Exactly(2), "A map with only the listed keys"),
+    builtin(
+        "sorted-pairs",
+        Exactly(1),
+        "Map entries as pairs sorted by key",
+    ),
+    builtin("merge", AtLeast(1), "Combine maps, later keys winning"),
+    builtin("count", Exactly(1), "Number of elements"),
+    builtin("repeat", Exactly(2), "A vector of `n` copies of a value"),
+    builtin("assoc-in", Exactly(3), "Replace a value in nested vectors"),
+    builtin("assoc-vec", Exactly(3), "Replace the element at an index"),
+    builtin(
+        "update",
+        Exactly(3),
+        "Apply a function to the element at an index",
+    ),
+    builtin(
+        "vec->array",
+        Exactly(2),
+        "Convert a vector to a fixed-size array",
+    ),
+    builtin(
+        "make-grid",
+        Exactly(3),
+        "A rows-by-columns grid filled with a value",
+    ),
+    builtin(
+        "grid-ref",
+        Exactly(3),
+        "The cell of a grid at a row and column",
+    ),
+    builtin(
+        "not-empty",
+        Exactly(1),
+        "The collection, or None if it is empty",
+    ),
+    builtin("chunk-by", Exactly(2), "Split into runs sharing a key"),
+    builtin(
+        "group-by",
+        Exactly(2),
+        "Map each key to the elements that produced it",
+    ),
+    builtin(
+        "reductions",
+        Exactly(3),
+        "Every intermediate value of a fold",
+    ),
+    builtin(
+        "reduce-while",
+        Exactly(4),
+        "Fold while a predicate holds for the accumulator",
+    ),
+    builtin(
+        "iterate",
+        Exactly(3),
+        "The first `n` repeated applications of a function",
+    ),
+    builtin(
+        "mapcat",
+        Exactly(2),
+        "Map a function over a collection and concatenate the results",
+    ),
+    builtin(
+        "keep",
+        Exactly(2),
+        "The `Some` results of mapping a function over a collection",
+    ),
+    builtin(
+        "repeatedly",
+        Exactly(2),
+        "Collect the results of calling a function `n` times",
+    ),
+    builtin("max-by", Exactly(2), "The element with the largest key"),
+    builtin("min-by", Exactly(2), "The element with the smallest key"),
+    builtin(
+        "min-max",
+        Exactly(1),
+        "Smallest and largest element in one pass",
+    ),
+    builtin("most-common", Exactly(2), "The `n` most frequent elements"),
+    builtin(
+        "find-index",
+        Exactly(2),
+        "Position of the first element satisfying a predicate",
+    ),
+    // Mutable state
+    builtin("atom", Exactly(1), "A mutable cell holding a value"),
+    builtin("deref", Exactly(1), "The current value of an atom"),
+    builtin(
+        "swap!",
+        Exactly(2),
+        "Replace an atom's value with a function of it",
+    ),
+    builtin("inc!", Exactly(1), "Add one to a mutable variable"),
+    builtin("dec!", Exactly(1), "Subtract one from a mutable variable"),
+    // Strings
+    builtin(
+        "str",
+        AtLeast(0),
+        "Concatenate the display forms of the arguments",
+    ),
+    builtin("str-repeat", Exactly(2), "A string repeated `n` times"),
+    builtin("hex", Exactly(1), "A number in base 16, prefixed with 0x"),
+    builtin("bin", Exactly(1), "A number in base 2, prefixed with 0b"),
+    builtin("oct", Exactly(1), "A number in base 8, prefixed with 0o"),
+    builtin("pad-left", Exactly(2), "Right-align a value to a width"),
+    builtin("pad-right", Exactly(2), "Left-align a value to a width"),
+    // Math utilities
+    builtin("min", AtLeast(2), "Smallest argument"),
+    builtin("max", AtLeast(2), "Largest argument"),
+    builtin("gcd", AtLeast(2), "Greatest common divisor"),
+    builtin("lcm", AtLeast(2), "Least common multiple"),
+    builtin("abs", Exactly(1), "Absolute value"),
+    builtin("bool->int", Exactly(1), "1 for true, 0 for false"),
+    builtin(
+        "count-true",
+        Exactly(1),
+        "Number of true values in a collection",
+    ),
+    builtin(
+        "count-if",
+        Exactly(2),
+        "Number of elements satisfying a predicate",
+    ),
+    builtin("inc", Exactly(1), "Add one"),
+    builtin("dec", Exactly(1), "Subtract one"),
+    // Predicates
+    builtin("zero", Exactly(1), "Whether a number is zero"),
+    builtin("pos", Exactly(1), "Whether a number is positive"),
+    builtin("neg", Exactly(1), "Whether a number is negative"),
+    builtin("even", Exactly(1), "Whether a number is even"),
+    builtin("odd", Exactly(1), "Whether a number is odd"),
+    builtin("between?", Exactly(3), "Whether lo <= x <= hi"),
+    builtin("in-range?", Exactly(3), "Whether lo <= x < hi"),
+    builtin("truthy?", Exactly(1), "Whether an integer is non-zero"),
+    builtin("falsy?", Exactly(1), "Whether an integer is zero"),
+    // Environment
+    builtin(
+        "env",
+        Range(1, 2),
+        "An environment variable, with an optional default",
+    ),
+    builtin(
+        "read-line",
+        Exactly(0),
+        "A trimmed line from standard input",
+    ),
+    builtin("slurp", Exactly(1), "The contents of a file as a string"),
+    builtin("spit", Exactly(2), "Write a string to a file"),
+    builtin(
+        "try-slurp",
+        Exactly(1),
+        "Read a file, returning an `io::Result`",
+    ),
+    builtin(
+        "try-spit",
+        Exactly(2),
+        "Write a file, returning an `io::Result`",
+    ),
+    // Printing
+    builtin(
+        "println",
+        AtLeast(1),
+        "Print text as is and other values in debug form",
+    ),
+    builtin(
+        "pr-str",
+        Exactly(1),
+        "The debug form of a value as a string",
+    ),
+    builtin(
+        "print-table",
+        Exactly(1),
+        "Print each element on its own numbered line",
+    ),
+    builtin(
+        "time",
+        Exactly(1),
+        "Evaluate a form, printing the elapsed time",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LispExpr;
+
+    #[test]
+    fn arity_display_and_accepts() {
+        assert_eq!(Arity::Exactly(2).to_string(), "2");
+        assert_eq!(Arity::AtLeast(1).to_string(), "1+");
+        assert_eq!(Arity::Range(2, 3).to_string(), "2-3");
+        assert!(Arity::Range(2, 3).accepts(3));
+        assert!(!Arity::Exactly(1).accepts(0));
+    }
+
+    #[test]
+    fn names_are_unique() {
+        let mut names: Vec<_> = builtins().iter().map(|b| b.name).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), builtins().len());
+    }
+
+    #[test]
+    fn every_builtin_is_expanded() {
+        // An unknown head falls through to a plain function call, so any
+        // registered name that expands to one has no matching arm.
+        for builtin in builtins() {
+            let call = LispExpr::List(vec![LispExpr::Operator(builtin.name.to_string())]);
+            assert_ne!(
+                call.to_rust().to_string(),
+                format!("{} ()", crate::rust_name(builtin.name)),
+                "`{}` is registered but not handled",
+                builtin.name
+            );
+        }
+    }
+}
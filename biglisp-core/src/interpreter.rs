@@ -0,0 +1,1016 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use syn::Lit;
+
+use crate::value::{Lambda, NativeFunction};
+use crate::{IntoLispValue, LispExpr, LispValue, TypeMismatch};
+
+/// Errors produced while parsing or evaluating BigLisp at runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalError {
+    /// The source text could not be parsed.
+    Parse(String),
+
+    /// A symbol was referenced that has no binding.
+    UnboundSymbol(String),
+
+    /// An operation received a value of the wrong type.
+    TypeMismatch(TypeMismatch),
+
+    /// A form was used with the wrong shape or number of arguments.
+    InvalidForm(String),
+
+    /// The head of a list is not a known operation or function.
+    UnknownOperation(String),
+
+    /// An integer division or modulo by zero.
+    DivisionByZero,
+
+    /// Evaluation took more steps than the interpreter's configured limit.
+    StepLimitExceeded(u64),
+
+    /// Function calls nested deeper than the interpreter's call depth limit,
+    /// as in unbounded recursion.
+    ///
+    /// See [`Interpreter::set_max_call_depth`].
+    RecursionLimitExceeded(usize),
+}
+
+impl std::fmt::Display for EvalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvalError::Parse(msg) => write!(f, "parse error: {}", msg),
+            EvalError::UnboundSymbol(name) => write!(f, "unbound symbol: {}", name),
+            EvalError::TypeMismatch(err) => write!(f, "type mismatch: {}", err),
+            EvalError::InvalidForm(msg) => write!(f, "{}", msg),
+            EvalError::UnknownOperation(name) => write!(f, "unknown operation: {}", name),
+            EvalError::DivisionByZero => write!(f, "division by zero"),
+            EvalError::StepLimitExceeded(limit) => {
+                write!(f, "evaluation exceeded the step limit of {}", limit)
+            }
+            EvalError::RecursionLimitExceeded(limit) => {
+                write!(f, "function calls nested deeper than {}", limit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+impl From<TypeMismatch> for EvalError {
+    fn from(err: TypeMismatch) -> Self {
+        EvalError::TypeMismatch(err)
+    }
+}
+
+impl From<syn::Error> for EvalError {
+    fn from(err: syn::Error) -> Self {
+        EvalError::Parse(err.to_string())
+    }
+}
+
+/// How deeply function calls may nest, by default, before evaluation stops
+/// with [`EvalError::RecursionLimitExceeded`].
+///
+/// The interpreter recurses on the host's stack, so unlike the step limit
+/// this bound always applies: it keeps a runaway recursive script from
+/// overflowing the stack and aborting the host process. Each nested call
+/// uses roughly 3-4 KiB of stack in release builds, so the default fits the
+/// 8 MiB main thread with room to spare. Debug builds need about three times
+/// as much; hosts evaluating scripts there or on smaller threads should lower
+/// the limit with [`Interpreter::set_max_call_depth`].
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 1_000;
+
+/// Local bindings, innermost scope last.
+type Scopes = Vec<HashMap<String, LispValue>>;
+
+/// A tree-walking interpreter for BigLisp.
+///
+/// Where the `lisp!` macro compiles BigLisp into Rust at build time, the
+/// interpreter evaluates BigLisp source at runtime. Global definitions
+/// persist between calls to [`Interpreter::eval_str`], so a host program can
+/// seed data with [`Interpreter::define`], run scripts against it, and read
+/// the results back.
+///
+/// # Example
+/// ```rust
+/// use biglisp_core::{Interpreter, LispValue};
+///
+/// let mut interp = Interpreter::new();
+/// interp.define("price", 100);
+/// let total = interp.eval_str("(+ price (/ price 10))").unwrap();
+/// assert_eq!(total, LispValue::Int(110));
+/// ```
+pub struct Interpreter {
+    globals: HashMap<String, LispValue>,
+    step_limit: Option<u64>,
+    steps: u64,
+    max_call_depth: usize,
+    depth: usize,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self {
+            globals: HashMap::new(),
+            step_limit: None,
+            steps: 0,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            depth: 0,
+        }
+    }
+}
+
+impl Interpreter {
+    /// Creates an interpreter with no global definitions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits how many expressions a single evaluation may visit.
+    ///
+    /// Every expression evaluated counts as one step, including each pass
+    /// through a loop body. When a call to [`Interpreter::eval_str`] or
+    /// [`Interpreter::eval`] exceeds the limit it stops with
+    /// [`EvalError::StepLimitExceeded`], which protects hosts from runaway
+    /// scripts such as `(while true 0)`. `None` removes the limit.
+    pub fn set_step_limit(&mut self, limit: Option<u64>) {
+        self.step_limit = limit;
+    }
+
+    /// Limits how deeply function calls may nest.
+    ///
+    /// A call that would nest deeper stops evaluation with
+    /// [`EvalError::RecursionLimitExceeded`]. The limit defaults to
+    /// [`DEFAULT_MAX_CALL_DEPTH`]; lower it when scripts run on a thread with
+    /// a small stack, or raise it for deeply recursive scripts on a large one.
+    pub fn set_max_call_depth(&mut self, depth: usize) {
+        self.max_call_depth = depth;
+    }
+
+    /// Binds a global variable visible to every subsequently evaluated script.
+    ///
+    /// Redefining an existing name replaces its value.
+    pub fn define<T: IntoLispValue>(&mut self, name: &str, value: T) {
+        self.globals
+            .insert(name.to_string(), value.into_lisp_value());
+    }
+
+    /// Exposes a native Rust function to BigLisp scripts under `name`.
+    ///
+    /// The function receives its arguments already evaluated and can be called
+    /// like any `defn` function, either directly as `(name args...)` or through
+    /// `call`. Errors it returns propagate out of `eval_str` unchanged.
+    ///
+    /// # Example
+    /// ```rust
+    /// use biglisp_core::{EvalError, Interpreter, LispValue};
+    ///
+    /// let mut interp = Interpreter::new();
+    /// interp.register_fn("twice", |args: &[LispValue]| match args {
+    ///     [LispValue::Int(n)] => Ok(LispValue::Int(n * 2)),
+    ///     _ => Err(EvalError::InvalidForm("twice requires one int".to_string())),
+    /// });
+    /// assert_eq!(interp.eval_str("(twice 4)"), Ok(LispValue::Int(8)));
+    /// ```
+    pub fn register_fn<F>(&mut self, name: &str, func: F)
+    where
+        F: Fn(&[LispValue]) -> Result<LispValue, EvalError> + 'static,
+    {
+        let native = NativeFunction {
+            name: name.to_string(),
+            func: Box::new(func),
+        };
+        self.define(name, LispValue::NativeFunction(Rc::new(native)));
+    }
+
+    /// Returns the value of a global variable, if it is defined.
+    pub fn get(&self, name: &str) -> Option<&LispValue> {
+        self.globals.get(name)
+    }
+
+    /// Parses and evaluates every top-level form in `src`.
+    ///
+    /// # Returns
+    /// - `Ok(LispValue)`: The value of the last form, or `Nil` for empty input.
+    /// - `Err(EvalError)`: If parsing fails or any form fails to evaluate.
+    pub fn eval_str(&mut self, src: &str) -> Result<LispValue, EvalError> {
+        let forms = LispExpr::parse_program(src)?;
+        self.steps = 0;
+        let mut result = LispValue::Nil;
+        for form in &forms {
+            result = self.eval_in(form, &mut Vec::new())?;
+        }
+        Ok(result)
+    }
+
+    /// Evaluates a single already-parsed expression in the global scope.
+    pub fn eval(&mut self, expr: &LispExpr) -> Result<LispValue, EvalError> {
+        self.steps = 0;
+        self.eval_in(expr, &mut Vec::new())
+    }
+
+    fn eval_in(&mut self, expr: &LispExpr, scopes: &mut Scopes) -> Result<LispValue, EvalError> {
+        self.steps += 1;
+        if let Some(limit) = self.step_limit {
+            if self.steps > limit {
+                return Err(EvalError::StepLimitExceeded(limit));
+            }
+        }
+        match expr {
+            LispExpr::Literal(lit) => eval_literal(lit),
+            LispExpr::Char(c) => Ok(LispValue::Str(c.value().to_string())),
+            LispExpr::Symbol(ident) => self.lookup(&ident.to_string(), scopes),
+            LispExpr::Operator(op) => Err(EvalError::InvalidForm(format!(
+                "Operator {} cannot be used as a value",
+                op
+            ))),
+            LispExpr::Keyword(name) => Err(EvalError::InvalidForm(format!(
+                "Keyword :{} is only valid inside a special form",
+                name
+            ))),
+            LispExpr::Vector(items) => {
+                let values = self.eval_all(items, scopes)?;
+                Ok(LispValue::Vector(values))
+            }
+            LispExpr::List(exprs) => {
+                if exprs.is_empty() {
+                    return Ok(LispValue::Nil);
+                }
+                let rest = &exprs[1..];
+                match &exprs[0] {
+                    LispExpr::Symbol(op) => self.eval_operation(&op.to_string(), rest, scopes),
+                    LispExpr::Operator(op) => self.eval_operation(op, rest, scopes),
+                    head => {
+                        let func = self.eval_in(head, scopes)?;
+                        let args = self.eval_all(rest, scopes)?;
+                        self.apply(&func, args)
+                    }
+                }
+            }
+        }
+    }
+
+    fn eval_all(
+        &mut self,
+        exprs: &[LispExpr],
+        scopes: &mut Scopes,
+    ) -> Result<Vec<LispValue>, EvalError> {
+        exprs.iter().map(|e| self.eval_in(e, scopes)).collect()
+    }
+
+    /// Evaluates a sequence of body forms, returning the last value.
+    fn eval_body(
+        &mut self,
+        body: &[LispExpr],
+        scopes: &mut Scopes,
+    ) -> Result<LispValue, EvalError> {
+        let mut result = LispValue::Nil;
+        for expr in body {
+            result = self.eval_in(expr, scopes)?;
+        }
+        Ok(result)
+    }
+
+    fn lookup(&self, name: &str, scopes: &Scopes) -> Result<LispValue, EvalError> {
+        scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .or_else(|| self.globals.get(name))
+            .cloned()
+            .ok_or_else(|| EvalError::UnboundSymbol(name.to_string()))
+    }
+
+    /// Binds `name` in the innermost local scope, or globally at the top level.
+    fn bind(&mut self, name: String, value: LispValue, scopes: &mut Scopes) {
+        match scopes.last_mut() {
+            Some(scope) => {
+                scope.insert(name, value);
+            }
+            None => {
+                self.globals.insert(name, value);
+            }
+        }
+    }
+
+    /// Evaluates a list whose head is a symbol or operator.
+    ///
+    /// The supported forms mirror the ones `expand_operation` lowers to Rust,
+    /// with the same argument rules and error messages.
+    fn eval_operation(
+        &mut self,
+        op: &str,
+        args: &[LispExpr],
+        scopes: &mut Scopes,
+    ) -> Result<LispValue, EvalError> {
+        match op {
+            "+" | "*" | "-" | "/" | "%" | "modulo" | "=" | "eq" | "ne" | "not=" | "<" | ">"
+            | "lte" | "gte" | "and" | "or" | "not" | "min" | "max" | "abs" | "inc" | "dec"
+            | "zero" | "pos" | "neg" | "even" | "odd" => self.eval_arithmetic(op, args, scopes),
+            "if" | "let" | "do" | "while" | "dotimes" | "doseq" => {
+                self.eval_control(op, args, scopes)
+            }
+            "def" | "defn" | "call" => self.eval_definition(op, args, scopes),
+            "first" | "rest" | "cons" | "count" | "str" | "println" => {
+                self.eval_collection(op, args, scopes)
+            }
+
+            // Default: call a function bound to the operation name, whose
+            // hyphens were turned into underscores when it was defined
+            _ => match self.lookup(&crate::rust_name(op), scopes) {
+                Ok(func @ (LispValue::Function(_) | LispValue::NativeFunction(_))) => {
+                    let values = self.eval_all(args, scopes)?;
+                    self.apply(&func, values)
+                }
+                _ => Err(EvalError::UnknownOperation(op.to_string())),
+            },
+        }
+    }
+
+    /// Arithmetic, comparison, boolean and numeric predicate forms.
+    fn eval_arithmetic(
+        &mut self,
+        op: &str,
+        args: &[LispExpr],
+        scopes: &mut Scopes,
+    ) -> Result<LispValue, EvalError> {
+        match op {
+            // Arithmetic Operations
+            "+" => {
+                let values = self.eval_all(args, scopes)?;
+                values
+                    .iter()
+                    .try_fold(LispValue::Int(0), |acc, v| arithmetic("+", &acc, v))
+            }
+            "*" => {
+                let values = self.eval_all(args, scopes)?;
+                values
+                    .iter()
+                    .try_fold(LispValue::Int(1), |acc, v| arithmetic("*", &acc, v))
+            }
+            "-" => {
+                let values = self.eval_all(args, scopes)?;
+                match values.as_slice() {
+                    [] => Err(invalid("Subtraction requires at least 1 argument")),
+                    [only] => arithmetic("-", &LispValue::Int(0), only),
+                    [first, rest @ ..] => rest
+                        .iter()
+                        .try_fold(first.clone(), |acc, v| arithmetic("-", &acc, v)),
+                }
+            }
+            "/" => {
+                if args.len() < 2 {
+                    return Err(invalid("Division requires at least 2 arguments"));
+                }
+                let values = self.eval_all(args, scopes)?;
+                values[1..]
+                    .iter()
+                    .try_fold(values[0].clone(), |acc, v| arithmetic("/", &acc, v))
+            }
+            "%" | "modulo" => {
+                let [left, right] =
+                    self.eval_exact::<2>(args, scopes, "Modulo requires exactly 2 arguments")?;
+                arithmetic("%", &left, &right)
+            }
+
+            // Comparison operators
+            "=" | "eq" => {
+                let [left, right] =
+                    self.eval_exact::<2>(args, scopes, "Equality requires exactly 2 arguments")?;
+                Ok(LispValue::Bool(values_equal(&left, &right)))
+            }
+            "ne" | "not=" => {
+                let [left, right] =
+                    self.eval_exact::<2>(args, scopes, "Not-equal requires exactly 2 arguments")?;
+                Ok(LispValue::Bool(!values_equal(&left, &right)))
+            }
+            "<" => self.compare(args, scopes, "Less-than", |o| o.is_lt()),
+            ">" => self.compare(args, scopes, "Greater-than", |o| o.is_gt()),
+            "lte" => self.compare(args, scopes, "Less-than-or-equal", |o| o.is_le()),
+            "gte" => self.compare(args, scopes, "Greater-than-or-equal", |o| o.is_ge()),
+
+            // Boolean operations
+            "and" => {
+                for arg in args {
+                    if !expect_bool(self.eval_in(arg, scopes)?)? {
+                        return Ok(LispValue::Bool(false));
+                    }
+                }
+                Ok(LispValue::Bool(true))
+            }
+            "or" => {
+                for arg in args {
+                    if expect_bool(self.eval_in(arg, scopes)?)? {
+                        return Ok(LispValue::Bool(true));
+                    }
+                }
+                Ok(LispValue::Bool(false))
+            }
+            "not" => {
+                let [arg] =
+                    self.eval_exact::<1>(args, scopes, "Not requires exactly 1 argument")?;
+                Ok(LispValue::Bool(!expect_bool(arg)?))
+            }
+
+            // Math utility functions
+            "min" | "max" => {
+                if args.len() < 2 {
+                    return Err(EvalError::InvalidForm(format!(
+                        "{} requires at least 2 arguments",
+                        op
+                    )));
+                }
+                let values = self.eval_all(args, scopes)?;
+                let mut best = values[0].clone();
+                for value in &values[1..] {
+                    let ordering = compare_values(value, &best)?;
+                    if (op == "min" && ordering.is_lt()) || (op == "max" && ordering.is_gt()) {
+                        best = value.clone();
+                    }
+                }
+                Ok(best)
+            }
+            "abs" => match self.eval_exact::<1>(args, scopes, "abs requires exactly 1 argument")? {
+                [LispValue::Int(n)] => n
+                    .checked_abs()
+                    .map(LispValue::Int)
+                    .ok_or_else(|| invalid("Integer overflow in abs")),
+                [LispValue::Float(n)] => Ok(LispValue::Float(n.abs())),
+                [other] => Err(mismatch("number", &other)),
+            },
+            "inc" => {
+                let [arg] =
+                    self.eval_exact::<1>(args, scopes, "inc requires exactly 1 argument")?;
+                arithmetic("+", &arg, &LispValue::Int(1))
+            }
+            "dec" => {
+                let [arg] =
+                    self.eval_exact::<1>(args, scopes, "dec requires exactly 1 argument")?;
+                arithmetic("-", &arg, &LispValue::Int(1))
+            }
+
+            // Predicate functions
+            "zero" | "pos" | "neg" | "even" | "odd" => {
+                let message = format!("{} requires exactly 1 argument", op);
+                let [arg] = self.eval_exact::<1>(args, scopes, &message)?;
+                let result = match (op, &arg) {
+                    ("zero", LispValue::Int(n)) => *n == 0,
+                    ("zero", LispValue::Float(n)) => *n == 0.0,
+                    ("pos", LispValue::Int(n)) => *n > 0,
+                    ("pos", LispValue::Float(n)) => *n > 0.0,
+                    ("neg", LispValue::Int(n)) => *n < 0,
+                    ("neg", LispValue::Float(n)) => *n < 0.0,
+                    ("even", LispValue::Int(n)) => n % 2 == 0,
+                    ("odd", LispValue::Int(n)) => n % 2 != 0,
+                    _ => return Err(mismatch("number", &arg)),
+                };
+                Ok(LispValue::Bool(result))
+            }
+            _ => Err(EvalError::UnknownOperation(op.to_string())),
+        }
+    }
+
+    /// Conditionals, local bindings and loops.
+    fn eval_control(
+        &mut self,
+        op: &str,
+        args: &[LispExpr],
+        scopes: &mut Scopes,
+    ) -> Result<LispValue, EvalError> {
+        match op {
+            // Control flow
+            "if" => {
+                if args.len() != 2 && args.len() != 3 {
+                    return Err(invalid("If requires 2 or 3 arguments"));
+                }
+                if expect_bool(self.eval_in(&args[0], scopes)?)? {
+                    self.eval_in(&args[1], scopes)
+                } else if let Some(else_branch) = args.get(2) {
+                    self.eval_in(else_branch, scopes)
+                } else {
+                    Ok(LispValue::Nil)
+                }
+            }
+            "let" => {
+                if args.len() < 2 {
+                    return Err(invalid("Let requires bindings and body"));
+                }
+                let LispExpr::Vector(bindings) = &args[0] else {
+                    return Err(invalid("Let requires vector of bindings"));
+                };
+                scopes.push(HashMap::new());
+                let result = self.eval_let(bindings, &args[1..], scopes);
+                scopes.pop();
+                result
+            }
+            "do" => self.eval_body(args, scopes),
+            "while" => {
+                if args.len() != 2 {
+                    return Err(invalid("While requires condition and body"));
+                }
+                while expect_bool(self.eval_in(&args[0], scopes)?)? {
+                    self.eval_in(&args[1], scopes)?;
+                }
+                Ok(LispValue::Nil)
+            }
+            "dotimes" => {
+                if args.len() != 3 {
+                    return Err(invalid("dotimes requires var, count, and body"));
+                }
+                let LispExpr::Symbol(var) = &args[0] else {
+                    return Err(invalid("dotimes requires variable name"));
+                };
+                let count = expect_int(self.eval_in(&args[1], scopes)?)?;
+                scopes.push(HashMap::new());
+                let result = (0..count).try_for_each(|i| {
+                    self.bind(var.to_string(), LispValue::Int(i), scopes);
+                    self.eval_in(&args[2], scopes).map(|_| ())
+                });
+                scopes.pop();
+                result.map(|_| LispValue::Nil)
+            }
+            "doseq" => {
+                let [LispExpr::Vector(binding), body] = args else {
+                    return Err(invalid("doseq requires a binding vector and body"));
+                };
+                let [LispExpr::Symbol(var), coll] = binding.as_slice() else {
+                    return Err(invalid(
+                        "doseq requires a binding vector of the form [var coll]",
+                    ));
+                };
+                let items = expect_vec(self.eval_in(coll, scopes)?)?;
+                scopes.push(HashMap::new());
+                let result = items.into_iter().try_for_each(|item| {
+                    self.bind(var.to_string(), item, scopes);
+                    self.eval_in(body, scopes).map(|_| ())
+                });
+                scopes.pop();
+                result.map(|_| LispValue::Nil)
+            }
+            _ => Err(EvalError::UnknownOperation(op.to_string())),
+        }
+    }
+
+    /// Global definitions and explicit calls.
+    fn eval_definition(
+        &mut self,
+        op: &str,
+        args: &[LispExpr],
+        scopes: &mut Scopes,
+    ) -> Result<LispValue, EvalError> {
+        match op {
+            // Definitions and functions
+            "def" => {
+                let [LispExpr::Symbol(name), value] = args else {
+                    return Err(invalid("def format: (def name value)"));
+                };
+                let value = self.eval_in(value, scopes)?;
+                self.bind(name.to_string(), value.clone(), scopes);
+                Ok(value)
+            }
+            "defn" => {
+                if args.len() < 3 {
+                    return Err(invalid(
+                        "Function definition requires name, params, and body",
+                    ));
+                }
+                let (LispExpr::Symbol(name), LispExpr::Vector(params)) = (&args[0], &args[1])
+                else {
+                    return Err(invalid(
+                        "Function definition format: (defn name [params] body)",
+                    ));
+                };
+                let params = params
+                    .iter()
+                    .map(|p| match p {
+                        LispExpr::Symbol(s) => Ok(s.to_string()),
+                        _ => Err(invalid("Function parameters must be symbols")),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let captured = scopes
+                    .iter()
+                    .flatten()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect();
+                let func = LispValue::Function(Rc::new(Lambda {
+                    name: name.to_string(),
+                    params,
+                    body: args[2..].to_vec(),
+                    captured,
+                }));
+                self.bind(name.to_string(), func.clone(), scopes);
+                Ok(func)
+            }
+            "call" => {
+                let Some((func, func_args)) = args.split_first() else {
+                    return Err(invalid("call requires at least a function"));
+                };
+                let func = self.eval_in(func, scopes)?;
+                let func_args = self.eval_all(func_args, scopes)?;
+                self.apply(&func, func_args)
+            }
+            _ => Err(EvalError::UnknownOperation(op.to_string())),
+        }
+    }
+
+    /// List, string and printing forms.
+    fn eval_collection(
+        &mut self,
+        op: &str,
+        args: &[LispExpr],
+        scopes: &mut Scopes,
+    ) -> Result<LispValue, EvalError> {
+        match op {
+            // List/Vector operations
+            "first" => {
+                let [coll] =
+                    self.eval_exact::<1>(args, scopes, "First requires exactly 1 argument")?;
+                Ok(expect_vec(coll)?
+                    .into_iter()
+                    .next()
+                    .unwrap_or(LispValue::Nil))
+            }
+            "rest" => {
+                let [coll] =
+                    self.eval_exact::<1>(args, scopes, "Rest requires exactly 1 argument")?;
+                Ok(LispValue::Vector(
+                    expect_vec(coll)?.into_iter().skip(1).collect(),
+                ))
+            }
+            "cons" => {
+                let [elem, coll] =
+                    self.eval_exact::<2>(args, scopes, "Cons requires exactly 2 arguments")?;
+                let mut items = vec![elem];
+                items.extend(expect_vec(coll)?);
+                Ok(LispValue::Vector(items))
+            }
+            "count" => {
+                let [coll] =
+                    self.eval_exact::<1>(args, scopes, "Count requires exactly 1 argument")?;
+                match coll {
+                    LispValue::Str(s) => Ok(LispValue::Int(s.chars().count() as i64)),
+                    other => Ok(LispValue::Int(expect_vec(other)?.len() as i64)),
+                }
+            }
+
+            // String operations
+            "str" => {
+                let values = self.eval_all(args, scopes)?;
+                Ok(LispValue::Str(
+                    values.iter().map(|v| v.to_string()).collect(),
+                ))
+            }
+
+            // Print/debug
+            "println" => {
+                let values = self.eval_all(args, scopes)?;
+                let line: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+                println!("{}", line.join(" "));
+                Ok(LispValue::Nil)
+            }
+            _ => Err(EvalError::UnknownOperation(op.to_string())),
+        }
+    }
+
+    fn eval_let(
+        &mut self,
+        bindings: &[LispExpr],
+        body: &[LispExpr],
+        scopes: &mut Scopes,
+    ) -> Result<LispValue, EvalError> {
+        for binding in bindings.chunks(2) {
+            let [LispExpr::Symbol(name), value] = binding else {
+                return Err(invalid("Let bindings must be symbol/value pairs"));
+            };
+            let value = self.eval_in(value, scopes)?;
+            self.bind(name.to_string(), value, scopes);
+        }
+        self.eval_body(body, scopes)
+    }
+
+    /// Evaluates exactly `N` arguments, failing with `message` on any other count.
+    fn eval_exact<const N: usize>(
+        &mut self,
+        args: &[LispExpr],
+        scopes: &mut Scopes,
+        message: &str,
+    ) -> Result<[LispValue; N], EvalError> {
+        if args.len() != N {
+            return Err(invalid(message));
+        }
+        let values = self.eval_all(args, scopes)?;
+        Ok(values.try_into().expect("argument count checked above"))
+    }
+
+    fn compare(
+        &mut self,
+        args: &[LispExpr],
+        scopes: &mut Scopes,
+        name: &str,
+        test: fn(std::cmp::Ordering) -> bool,
+    ) -> Result<LispValue, EvalError> {
+        let message = format!("{} requires exactly 2 arguments", name);
+        let [left, right] = self.eval_exact::<2>(args, scopes, &message)?;
+        Ok(LispValue::Bool(test(compare_values(&left, &right)?)))
+    }
+
+    /// Calls a function value with already-evaluated arguments.
+    fn apply(&mut self, func: &LispValue, args: Vec<LispValue>) -> Result<LispValue, EvalError> {
+        let lambda = match func {
+            LispValue::Function(lambda) => lambda,
+            LispValue::NativeFunction(native) => return (native.func)(&args),
+            _ => return Err(mismatch("function", func)),
+        };
+        if args.len() != lambda.params.len() {
+            return Err(EvalError::InvalidForm(format!(
+                "{} expects {} arguments, got {}",
+                lambda.name,
+                lambda.params.len(),
+                args.len()
+            )));
+        }
+        let mut frame = lambda.captured.clone();
+        // Make the function visible to itself so local definitions can recurse.
+        frame.insert(lambda.name.clone(), func.clone());
+        frame.extend(lambda.params.iter().cloned().zip(args));
+        let mut scopes = vec![frame];
+        if self.depth >= self.max_call_depth {
+            return Err(EvalError::RecursionLimitExceeded(self.max_call_depth));
+        }
+        self.depth += 1;
+        let result = self.eval_body(&lambda.body, &mut scopes);
+        self.depth -= 1;
+        result
+    }
+}
+
+fn invalid(message: &str) -> EvalError {
+    EvalError::InvalidForm(message.to_string())
+}
+
+fn mismatch(expected: &'static str, found: &LispValue) -> EvalError {
+    EvalError::TypeMismatch(TypeMismatch {
+        expected,
+        found: found.type_name(),
+    })
+}
+
+fn expect_bool(value: LispValue) -> Result<bool, EvalError> {
+    Ok(bool::try_from(value)?)
+}
+
+fn expect_int(value: LispValue) -> Result<i64, EvalError> {
+    Ok(i64::try_from(value)?)
+}
+
+fn expect_vec(value: LispValue) -> Result<Vec<LispValue>, EvalError> {
+    match value {
+        LispValue::Vector(items) => Ok(items),
+        other => Err(mismatch("vector", &other)),
+    }
+}
+
+fn eval_literal(lit: &Lit) -> Result<LispValue, EvalError> {
+    match lit {
+        Lit::Int(n) => Ok(LispValue::Int(n.base10_parse()?)),
+        Lit::Float(n) => Ok(LispValue::Float(n.base10_parse()?)),
+        Lit::Str(s) => Ok(LispValue::Str(s.value())),
+        Lit::Bool(b) => Ok(LispValue::Bool(b.value)),
+        Lit::Char(c) => Ok(LispValue::Str(c.value().to_string())),
+        _ => Err(invalid("Unsupported literal")),
+    }
+}
+
+/// Applies a binary arithmetic operator, promoting to float if either side is a float.
+fn arithmetic(op: &str, left: &LispValue, right: &LispValue) -> Result<LispValue, EvalError> {
+    match (left, right) {
+        (LispValue::Int(a), LispValue::Int(b)) => {
+            let result = match op {
+                "+" => a.checked_add(*b),
+                "-" => a.checked_sub(*b),
+                "*" => a.checked_mul(*b),
+                "/" | "%" if *b == 0 => return Err(EvalError::DivisionByZero),
+                "/" => a.checked_div(*b),
+                "%" => a.checked_rem(*b),
+                _ => unreachable!("unknown arithmetic operator {}", op),
+            };
+            result
+                .map(LispValue::Int)
+                .ok_or_else(|| EvalError::InvalidForm(format!("Integer overflow in {}", op)))
+        }
+        (LispValue::Int(_) | LispValue::Float(_), LispValue::Int(_) | LispValue::Float(_)) => {
+            let (a, b) = (as_f64(left), as_f64(right));
+            let result = match op {
+                "+" => a + b,
+                "-" => a - b,
+                "*" => a * b,
+                "/" => a / b,
+                "%" => a % b,
+                _ => unreachable!("unknown arithmetic operator {}", op),
+            };
+            Ok(LispValue::Float(result))
+        }
+        (LispValue::Int(_) | LispValue::Float(_), other) | (other, _) => {
+            Err(mismatch("number", other))
+        }
+    }
+}
+
+fn as_f64(value: &LispValue) -> f64 {
+    match value {
+        LispValue::Int(n) => *n as f64,
+        LispValue::Float(n) => *n,
+        _ => f64::NAN,
+    }
+}
+
+/// Equality that treats `1` and `1.0` as equal, like the numeric comparisons do.
+fn values_equal(left: &LispValue, right: &LispValue) -> bool {
+    match (left, right) {
+        (LispValue::Int(_), LispValue::Float(_)) | (LispValue::Float(_), LispValue::Int(_)) => {
+            as_f64(left) == as_f64(right)
+        }
+        _ => left == right,
+    }
+}
+
+fn compare_values(left: &LispValue, right: &LispValue) -> Result<std::cmp::Ordering, EvalError> {
+    match (left, right) {
+        (LispValue::Int(a), LispValue::Int(b)) => Ok(a.cmp(b)),
+        (LispValue::Str(a), LispValue::Str(b)) => Ok(a.cmp(b)),
+        (LispValue::Int(_) | LispValue::Float(_), LispValue::Int(_) | LispValue::Float(_)) => {
+            as_f64(left)
+                .partial_cmp(&as_f64(right))
+                .ok_or_else(|| invalid("Cannot compare NaN"))
+        }
+        (LispValue::Int(_) | LispValue::Float(_) | LispValue::Str(_), other) | (other, _) => {
+            Err(mismatch("number", other))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eval_arithmetic() {
+        let mut interp = Interpreter::new();
+        assert_eq!(interp.eval_str("(+ 1 2 3)"), Ok(LispValue::Int(6)));
+        assert_eq!(
+            interp.eval_str("(* (+ 1 2) (- 5 1))"),
+            Ok(LispValue::Int(12))
+        );
+        assert_eq!(interp.eval_str("(/ 7.0 2)"), Ok(LispValue::Float(3.5)));
+        assert_eq!(interp.eval_str("(/ 1 0)"), Err(EvalError::DivisionByZero));
+    }
+
+    #[test]
+    fn host_defined_variable() {
+        let mut interp = Interpreter::new();
+        interp.define("prices", vec![10, 20, 30]);
+        interp.define("tax", 2);
+
+        let result = interp
+            .eval_str("(let [total (+ (first prices) (count prices))] (* total tax))")
+            .unwrap();
+        assert_eq!(i64::try_from(result), Ok(26));
+    }
+
+    #[test]
+    fn state_persists_between_calls() {
+        let mut interp = Interpreter::new();
+        interp.eval_str("(def base 40)").unwrap();
+        interp.eval_str("(defn add_base [x] (+ x base))").unwrap();
+        assert_eq!(interp.eval_str("(add_base 2)"), Ok(LispValue::Int(42)));
+        assert_eq!(interp.get("base"), Some(&LispValue::Int(40)));
+    }
+
+    #[test]
+    fn recursion_and_closures() {
+        let mut interp = Interpreter::new();
+        let src = "
+            (defn fact [n] (if (lte n 1) 1 (* n (fact (- n 1)))))
+            (fact 5)
+        ";
+        assert_eq!(interp.eval_str(src), Ok(LispValue::Int(120)));
+
+        let adder = interp
+            .eval_str("(let [k 10] (defn add_k [x] (+ x k)))")
+            .unwrap();
+        assert_eq!(
+            interp.apply(&adder, vec![LispValue::Int(5)]),
+            Ok(LispValue::Int(15))
+        );
+    }
+
+    #[test]
+    fn hyphenated_names() {
+        let mut interp = Interpreter::new();
+        let src = "
+            (defn price-with-tax [price] (+ price (/ price 10)))
+            (let [total-count 50] (price-with-tax total-count))
+        ";
+        assert_eq!(interp.eval_str(src), Ok(LispValue::Int(55)));
+    }
+
+    #[test]
+    fn native_functions() {
+        let mut interp = Interpreter::new();
+        interp.register_fn(
+            "double",
+            Box::new(|args: &[LispValue]| match args {
+                [LispValue::Int(n)] => Ok(LispValue::Int(n * 2)),
+                _ => Err(EvalError::InvalidForm(
+                    "double requires one int".to_string(),
+                )),
+            }),
+        );
+
+        assert_eq!(interp.eval_str("(double 21)"), Ok(LispValue::Int(42)));
+        assert_eq!(
+            interp.eval_str("(call double (+ 1 2))"),
+            Ok(LispValue::Int(6))
+        );
+        assert_eq!(
+            interp.eval_str("(double true)"),
+            Err(EvalError::InvalidForm(
+                "double requires one int".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn step_limit_stops_infinite_loops() {
+        let mut interp = Interpreter::new();
+        interp.set_step_limit(Some(1_000));
+        assert_eq!(
+            interp.eval_str("(while true (+ 1 1))"),
+            Err(EvalError::StepLimitExceeded(1_000))
+        );
+
+        // The budget is per evaluation, so ordinary scripts keep working
+        assert_eq!(interp.eval_str("(+ 1 2)"), Ok(LispValue::Int(3)));
+
+        interp.set_step_limit(None);
+        assert_eq!(interp.eval_str("(dotimes i 500 i)"), Ok(LispValue::Nil));
+    }
+
+    #[test]
+    fn recursion_limit_stops_unbounded_recursion() {
+        // Test threads have small stacks, so stay well below the default
+        let mut interp = Interpreter::new();
+        interp.set_max_call_depth(64);
+        interp.set_step_limit(Some(1_000));
+        assert_eq!(
+            interp.eval_str("(defn f [x] (f x)) (f 1)"),
+            Err(EvalError::RecursionLimitExceeded(64))
+        );
+
+        // The limit applies without a step limit too, and the depth unwinds
+        // so later calls start from the top again
+        interp.set_step_limit(None);
+        assert_eq!(
+            interp.eval_str("(f 1)"),
+            Err(EvalError::RecursionLimitExceeded(64))
+        );
+        assert_eq!(
+            interp.eval_str("(defn down [n] (if (= n 0) 0 (+ 1 (down (- n 1))))) (down 50)"),
+            Ok(LispValue::Int(50))
+        );
+    }
+
+    #[test]
+    fn default_call_depth_allows_ordinary_recursion() {
+        let mut interp = Interpreter::new();
+        assert_eq!(
+            interp.eval_str("(defn down [n] (if (= n 0) 0 (down (- n 1)))) (down 100)"),
+            Ok(LispValue::Int(0))
+        );
+    }
+
+    #[test]
+    fn eval_errors() {
+        let mut interp = Interpreter::new();
+        assert_eq!(
+            interp.eval_str("(+ missing 1)"),
+            Err(EvalError::UnboundSymbol("missing".to_string()))
+        );
+        assert_eq!(
+            interp.eval_str("(if 1 2 3)"),
+            Err(EvalError::TypeMismatch(TypeMismatch {
+                expected: "bool",
+                found: "int"
+            }))
+        );
+        assert!(matches!(interp.eval_str("(+ 1"), Err(EvalError::Parse(_))));
+        assert_eq!(
+            interp.eval_str("(abs (- 0 9223372036854775807 1))"),
+            Err(EvalError::InvalidForm(
+                "Integer overflow in abs".to_string()
+            ))
+        );
+    }
+}
@@ -1,17 +1,228 @@
+use std::cell::RefCell;
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use quote::{format_ident, quote, quote_spanned};
 use syn::{
-    parse::Parse,
+    ext::IdentExt,
+    parse::{discouraged::Speculative, Parse},
     token::{Bracket, Paren},
     Ident, Lit, Token,
 };
 
+mod builtins;
+mod interpreter;
+mod value;
+
+pub use builtins::{builtins, Arity, Builtin};
+pub use interpreter::{EvalError, Interpreter, DEFAULT_MAX_CALL_DEPTH};
+pub use value::{IntoLispValue, Lambda, LispValue, NativeFn, NativeFunction, TypeMismatch};
+
+/// Converts a hyphenated BigLisp name into a valid Rust identifier.
+///
+/// Hyphens become underscores, arrows become `_to_`, a trailing `?` becomes
+/// `_p` and a trailing `!` becomes `_bang`, so `total-count` is `total_count`,
+/// `bool->int` is `bool_to_int`, `empty?` is `empty_p` and `reset!` is
+/// `reset_bang`.
+pub(crate) fn rust_name(name: &str) -> String {
+    name.replace("->", "_to_")
+        .replace('-', "_")
+        .replace('?', "_p")
+        .replace('!', "_bang")
+}
+
+/// Creates a fresh identifier for a temporary in generated code.
+///
+/// Forms like `try` and `while-collect` bind helper variables around user
+/// code. Giving each one a unique, mixed-site name keeps them from shadowing
+/// (or being shadowed by) the user's own bindings such as `result`.
+fn gensym(name: &str) -> Ident {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    Ident::new(&format!("__{}_{}", name, n), Span::mixed_site())
+}
+
+/// Joins the expansions of `args` with a binary operator: `(a) + (b) + (c)`.
+///
+/// The terms go through a single `quote!` repetition. Rewrapping an
+/// accumulator once per operand would copy the whole expression each time,
+/// making expansion quadratic in the number of operands.
+fn infix_chain(args: &[LispExpr], op: TokenStream) -> TokenStream {
+    let first = args[0].to_rust();
+    let rest = args[1..].iter().map(|e| e.to_rust());
+    let ops = std::iter::repeat(op);
+    quote! { (#first) #(#ops (#rest))* }
+}
+
+/// Folds the expansions of `args` through a two-argument function, as in
+/// `f(f(a, b), c)`, by rebinding a temporary so the output grows linearly.
+fn call_chain(args: &[LispExpr], func: TokenStream) -> TokenStream {
+    let first = args[0].to_rust();
+    let rest = args[1..].iter().map(|e| e.to_rust());
+    let acc = gensym("acc");
+    quote! {
+        {
+            let #acc = #first;
+            #(let #acc = #func(#acc, #rest);)*
+            #acc
+        }
+    }
+}
+
+/// Names in scope, innermost last, each with the parameter count of the
+/// `defn` it is bound to. `None` marks a name bound to anything else, which
+/// hides any outer definition.
+type Bindings = Vec<(String, Option<usize>)>;
+
+thread_local! {
+    /// The bindings around the form being expanded; `None` outside of an
+    /// expansion.
+    static ARITIES: RefCell<Option<Bindings>> = const { RefCell::new(None) };
+}
+
+/// Runs `expand` with an arity table for `call` to consult, unless an
+/// enclosing expansion has already set one up.
+fn with_arities(expand: impl FnOnce() -> TokenStream) -> TokenStream {
+    if ARITIES.with(|arities| arities.borrow().is_some()) {
+        return expand();
+    }
+
+    // Clear the table even if expansion panics, so it can't leak into the
+    // next macro invocation on this thread.
+    struct Reset;
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            ARITIES.with(|arities| *arities.borrow_mut() = None);
+        }
+    }
+    ARITIES.with(|cell| *cell.borrow_mut() = Some(Vec::new()));
+    let _reset = Reset;
+    expand()
+}
+
+/// Runs `expand` in a new lexical scope, forgetting the names it binds with
+/// `bind_arity` once it returns.
+fn scoped<T>(expand: impl FnOnce() -> T) -> T {
+    let depth = ARITIES.with(|arities| arities.borrow().as_ref().map_or(0, Vec::len));
+    let result = expand();
+    ARITIES.with(|arities| {
+        if let Some(arities) = arities.borrow_mut().as_mut() {
+            arities.truncate(depth);
+        }
+    });
+    result
+}
+
+/// Records that `name` is bound to `value` for the rest of the current scope.
+/// A `defn` value makes its parameter count known to `call`; any other value,
+/// or `None` for parameters and loop variables, hides an outer definition.
+fn bind_arity(name: &Ident, value: Option<&LispExpr>) {
+    let arity = value
+        .and_then(|value| value.clause_args("defn"))
+        .and_then(|args| Defn::parse(args).ok())
+        .map(|defn| defn.params.len());
+    ARITIES.with(|arities| {
+        if let Some(arities) = arities.borrow_mut().as_mut() {
+            arities.push((name.to_string(), arity));
+        }
+    });
+}
+
+/// The parameter count of the `defn` that `name` is bound to in the
+/// innermost scope binding it, if any.
+fn arity_of(name: &Ident) -> Option<usize> {
+    let name = name.to_string();
+    ARITIES.with(|arities| {
+        let arities = arities.borrow();
+        let (_, arity) = arities
+            .as_ref()?
+            .iter()
+            .rev()
+            .find(|(bound, _)| *bound == name)?;
+        *arity
+    })
+}
+
+/// Hides outer definitions behind the symbols in the vector arguments of a
+/// form whose bindings aren't tracked individually, such as `doseq`, `for`
+/// or `dotimes`. Vectors nested in those vectors (`letfn` parameter lists)
+/// count too; plain list arguments such as bodies are not searched.
+fn shadow_vector_symbols(exprs: &[LispExpr], in_vector: bool) {
+    for expr in exprs {
+        match expr {
+            LispExpr::Symbol(name) if in_vector => bind_arity(name, None),
+            LispExpr::Vector(items) => shadow_vector_symbols(items, true),
+            LispExpr::List(items) if in_vector => shadow_vector_symbols(items, false),
+            _ => {}
+        }
+    }
+}
+
+/// Forms whose names end in `->`. These names stop at the arrow, so unlike
+/// `bool->int` they never join the identifier after it.
+const THREADING_FORMS: &[&str] = &["cond->", "as->", "some->"];
+
+/// Forms whose expansions rely on `std` (panic hooks, `String`, `HashMap`,
+/// I/O). Without the `std` feature they expand to a `compile_error!`.
+const STD_FORMS: &[&str] = &[
+    "try",
+    "try-verbose",
+    "str",
+    "str-repeat",
+    "hex",
+    "bin",
+    "oct",
+    "pad-left",
+    "pad-right",
+    "memoize",
+    "group-by",
+    "select-keys",
+    "env",
+    "read-line",
+    "slurp",
+    "spit",
+    "try-slurp",
+    "try-spit",
+    "println",
+    "pr-str",
+    "print-table",
+    "time",
+];
+
+/// Forms whose expansions build a `Vec` through `vec!`, `.to_vec()` or
+/// `collect`. Vector literals do too. A `#![no_std]` crate has neither in
+/// scope, so without the `std` feature these report that they need `alloc`.
+const ALLOC_FORMS: &[&str] = &[
+    "list",
+    "vec-of",
+    "cons",
+    "rest",
+    "butlast",
+    "nthrest",
+    "take-nth",
+    "split-at",
+    "slice",
+    "for",
+    "while-collect",
+    "repeat",
+    "repeatedly",
+    "make-grid",
+    "chunk-by",
+    "sorted-pairs",
+    "most-common",
+    "iterate",
+    "reductions",
+    "mapcat",
+    "keep",
+    "vec->array",
+];
+
 /// Represents a Lisp expression in the BigLisp language.
 ///
 /// This enum is used to model various types of expressions that can appear
 /// in Lisp-like syntax, including symbols, literals, lists, vectors, and operators.
+#[derive(Clone)]
 pub enum LispExpr {
     /// A symbol, represented by an identifier.
     Symbol(Ident),
@@ -19,6 +230,9 @@ pub enum LispExpr {
     /// A literal value, such as a number or string.
     Literal(Lit),
 
+    /// A character literal such as `'c'`.
+    Char(syn::LitChar),
+
     /// A list of Lisp expressions, typically used for function calls or grouping.
     List(Vec<LispExpr>),
 
@@ -27,6 +241,9 @@ pub enum LispExpr {
 
     /// An operator, represented as a string (e.g., "+", "-", "*").
     Operator(String),
+
+    /// A keyword such as `:when`, used to mark optional clauses in special forms.
+    Keyword(Ident),
 }
 
 impl Debug for LispExpr {
@@ -51,6 +268,12 @@ impl Debug for LispExpr {
             // Formats the `Operator` variant with its operator string.
             LispExpr::Operator(op) => write!(f, "Operator({})", op),
 
+            // Formats the `Keyword` variant with its leading colon.
+            LispExpr::Keyword(name) => write!(f, "Keyword(:{})", name),
+
+            // Formats the `Char` variant with its character.
+            LispExpr::Char(c) => write!(f, "Char({:?})", c.value()),
+
             // Formats the `List` variant by iterating over its elements.
             LispExpr::List(exprs) => {
                 write!(f, "List(")?;
@@ -78,15 +301,133 @@ impl Debug for LispExpr {
     }
 }
 
+/// The parts of a `(defn name [params] "doc"? body)` form.
+struct Defn<'a> {
+    name: &'a Ident,
+    params: Vec<&'a Ident>,
+    doc: Option<&'a syn::LitStr>,
+    body: &'a LispExpr,
+}
+
+impl<'a> Defn<'a> {
+    /// Splits the arguments of a `defn` form, returning a `compile_error!` for
+    /// malformed definitions. Non-symbol parameters are skipped.
+    fn parse(args: &'a [LispExpr]) -> Result<Self, TokenStream> {
+        if args.len() < 3 {
+            return Err(
+                quote! { compile_error!("Function definition requires name, params, and body") },
+            );
+        }
+        let (LispExpr::Symbol(name), LispExpr::Vector(params)) = (&args[0], &args[1]) else {
+            return Err(
+                quote! { compile_error!("Function definition format: (defn name [params] body)") },
+            );
+        };
+        let params = Defn::symbols(params);
+        // A string followed by another form is a docstring, not the body
+        let (doc, body) = match (&args[2], args.get(3)) {
+            (LispExpr::Literal(Lit::Str(doc)), Some(body)) => (Some(doc), body),
+            (body, _) => (None, body),
+        };
+        Ok(Defn {
+            name,
+            params,
+            doc,
+            body,
+        })
+    }
+
+    /// Splits a multi-arity `(defn name "doc"? ([params] body) ...)` into one
+    /// `Defn` per clause, or returns `None` if `args` is not of that shape.
+    fn parse_arities(args: &'a [LispExpr]) -> Option<Vec<Self>> {
+        let (LispExpr::Symbol(name), rest) = args.split_first()? else {
+            return None;
+        };
+        let (doc, clauses) = match rest.split_first()? {
+            (LispExpr::Literal(Lit::Str(doc)), clauses) => (Some(doc), clauses),
+            _ => (None, rest),
+        };
+        if clauses.is_empty() {
+            return None;
+        }
+        clauses
+            .iter()
+            .map(|clause| match clause {
+                LispExpr::List(items) => match items.as_slice() {
+                    [LispExpr::Vector(params), body] => Some(Defn {
+                        name,
+                        params: Defn::symbols(params),
+                        doc,
+                        body,
+                    }),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Generates one `fn` item per arity, rewriting calls to the function's
+    /// own name in each body to the arity-specific function.
+    fn to_rust_arities(arities: &[Defn]) -> TokenStream {
+        let counts: Vec<usize> = arities.iter().map(|defn| defn.params.len()).collect();
+        let items = arities.iter().map(|defn| {
+            let body = defn.body.clone().fold(&mut |expr| match expr {
+                LispExpr::List(mut items)
+                    if matches!(items.first(), Some(LispExpr::Symbol(head)) if head == defn.name)
+                        && counts.contains(&(items.len() - 1)) =>
+                {
+                    items[0] = LispExpr::Symbol(format_ident!("{}_{}", defn.name, items.len() - 1));
+                    LispExpr::List(items)
+                }
+                other => other,
+            });
+            let body_tokens = body.to_rust();
+            let fn_name = defn.arity_name();
+            let params = &defn.params;
+            let doc = defn.doc.map(|doc| quote! { #[doc = #doc] });
+            quote! {
+                #doc
+                fn #fn_name(#(#params: i32),*) -> i32 {
+                    #body_tokens
+                }
+            }
+        });
+        quote! { #(#items)* }
+    }
+
+    /// The name of the function generated for this arity, e.g. `sum_2`.
+    fn arity_name(&self) -> Ident {
+        format_ident!("{}_{}", self.name, self.params.len())
+    }
+
+    /// The symbols in a parameter vector; anything else is skipped.
+    fn symbols(params: &'a [LispExpr]) -> Vec<&'a Ident> {
+        params
+            .iter()
+            .filter_map(|p| {
+                if let LispExpr::Symbol(s) = p {
+                    Some(s)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
 impl Parse for LispExpr {
     /// Parses a `LispExpr` from a token stream.
     ///
     /// This implementation supports various Lisp-like syntax constructs, including:
     /// - Parenthesized lists of expressions (e.g., `(expr1 expr2 ...)`).
+    /// - Hyphenated names (e.g., `(chunk-by ...)`, `total-count`).
     /// - Bracketed vectors of expressions (e.g., `[expr1 expr2 ...]`).
     /// - Operators (`+`, `-`, `*`, `/`, `%`, etc.).
     /// - Literals (e.g., numbers, strings).
-    /// - Symbols (e.g., `if`, `let`, `do`, etc.).
+    /// - Keywords (e.g., `:when`).
+    /// - Symbols (e.g., `if`, `let`, `do`, `for`, etc.).
+    /// - `#_` discards, which skip the form that follows (e.g., `(+ 1 #_2 3)`).
     ///
     /// # Parameters
     /// - `input`: The token stream to parse.
@@ -95,12 +436,17 @@ impl Parse for LispExpr {
     /// - `Ok(LispExpr)`: The parsed Lisp expression.
     /// - `Err(syn::Error)`: If the input cannot be parsed as a valid `LispExpr`.
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        LispExpr::skip_discarded(input)?;
         if input.peek(Paren) {
             // Parse a parenthesized list of expressions.
             let content;
             syn::parenthesized!(content in input);
             let mut exprs = Vec::new();
-            while !content.is_empty() {
+            LispExpr::skip_discarded(&content)?;
+            if !content.is_empty() {
+                exprs.push(LispExpr::parse_list_head(&content)?);
+            }
+            while !LispExpr::skip_discarded(&content)?.is_empty() {
                 exprs.push(content.parse::<LispExpr>()?);
             }
             Ok(LispExpr::List(exprs))
@@ -109,7 +455,7 @@ impl Parse for LispExpr {
             let content;
             syn::bracketed!(content in input);
             let mut exprs = Vec::new();
-            while !content.is_empty() {
+            while !LispExpr::skip_discarded(&content)?.is_empty() {
                 exprs.push(content.parse::<LispExpr>()?);
             }
             Ok(LispExpr::Vector(exprs))
@@ -145,6 +491,13 @@ impl Parse for LispExpr {
             // Parse the `%` operator.
             input.parse::<Token![%]>()?;
             Ok(LispExpr::Operator("%".to_string()))
+        } else if input.peek(Token![:]) {
+            // Parse a keyword such as `:when`.
+            input.parse::<Token![:]>()?;
+            Ok(LispExpr::Keyword(Ident::parse_any(input)?))
+        } else if input.peek(syn::LitChar) {
+            // Parse a character literal.
+            Ok(LispExpr::Char(input.parse()?))
         } else if input.peek(Lit) {
             // Parse a literal value.
             Ok(LispExpr::Literal(input.parse()?))
@@ -153,48 +506,50 @@ impl Parse for LispExpr {
             let lookahead = input.lookahead1();
             if lookahead.peek(syn::Token![if]) {
                 // Parse the `if` symbol.
-                input.parse::<syn::Token![if]>()?;
-                Ok(LispExpr::Symbol(Ident::new("if", Span::call_site())))
+                let keyword = input.parse::<syn::Token![if]>()?;
+                Ok(LispExpr::Symbol(Ident::new("if", keyword.span)))
             } else if lookahead.peek(syn::Token![let]) {
                 // Parse the `let` symbol.
-                input.parse::<syn::Token![let]>()?;
-                Ok(LispExpr::Symbol(Ident::new("let", Span::call_site())))
+                let keyword = input.parse::<syn::Token![let]>()?;
+                Ok(LispExpr::Symbol(Ident::new("let", keyword.span)))
             } else if lookahead.peek(syn::Token![do]) {
                 // Parse the `do` symbol.
-                input.parse::<syn::Token![do]>()?;
-                Ok(LispExpr::Symbol(Ident::new("do", Span::call_site())))
+                let keyword = input.parse::<syn::Token![do]>()?;
+                Ok(LispExpr::Symbol(Ident::new("do", keyword.span)))
             } else if lookahead.peek(syn::Token![while]) {
                 // Parse the `while` symbol.
-                input.parse::<syn::Token![while]>()?;
-                Ok(LispExpr::Symbol(Ident::new("while", Span::call_site())))
+                let keyword = input.parse::<syn::Token![while]>()?;
+                Ok(LispExpr::Symbol(Ident::new("while", keyword.span)))
             } else if lookahead.peek(syn::Token![try]) {
                 // Parse the `try` symbol.
-                input.parse::<syn::Token![try]>()?;
-                Ok(LispExpr::Symbol(Ident::new("try", Span::call_site())))
+                let keyword = input.parse::<syn::Token![try]>()?;
+                Ok(LispExpr::Symbol(Ident::new("try", keyword.span)))
+            } else if lookahead.peek(syn::Token![for]) {
+                // Parse the `for` symbol.
+                let keyword = input.parse::<syn::Token![for]>()?;
+                Ok(LispExpr::Symbol(Ident::new("for", keyword.span)))
+            } else if lookahead.peek(syn::Token![as]) {
+                // Parse the `as` symbol, used by `as->`.
+                let keyword = input.parse::<syn::Token![as]>()?;
+                Ok(LispExpr::Symbol(Ident::new("as", keyword.span)))
+            } else if lookahead.peek(syn::Token![fn]) {
+                // Parse the `fn` symbol, used for anonymous functions.
+                let keyword = input.parse::<syn::Token![fn]>()?;
+                Ok(LispExpr::Symbol(Ident::new("fn", keyword.span)))
+            } else if lookahead.peek(syn::Token![in]) {
+                // Parse the `in` symbol, used by `in-range?`.
+                let keyword = input.parse::<syn::Token![in]>()?;
+                Ok(LispExpr::Symbol(Ident::new("in", keyword.span)))
             } else if lookahead.peek(Ident) {
-                // Parse an identifier or special symbol.
+                // Parse an identifier, joining hyphenated names like `total-count`
+                // into a single Rust-compatible symbol (`total_count`).
                 let ident: Ident = input.parse()?;
-                let ident_str = ident.to_string();
-                if ident_str == "defn"
-                    || ident_str == "println"
-                    || ident_str == "dotimes"
-                    || ident_str == "call"
-                    || ident_str == "gte"
-                    || ident_str == "lte"
-                    || ident_str == "ne"
-                    || ident_str == "min"
-                    || ident_str == "max"
-                    || ident_str == "abs"
-                    || ident_str == "modulo"
-                    || ident_str == "inc"
-                    || ident_str == "dec"
-                    || ident_str == "zero"
-                    || ident_str == "pos"
-                    || ident_str == "neg"
-                    || ident_str == "even"
-                    || ident_str == "odd"
-                {
-                    Ok(LispExpr::Symbol(ident))
+                let name = LispExpr::parse_hyphenated(&ident, input)?;
+                if ident != name {
+                    Ok(LispExpr::Symbol(Ident::new(
+                        &rust_name(&name),
+                        ident.span(),
+                    )))
                 } else {
                     Ok(LispExpr::Symbol(ident))
                 }
@@ -206,6 +561,305 @@ impl Parse for LispExpr {
     }
 }
 impl LispExpr {
+    /// Parses a whole BigLisp program from source text.
+    ///
+    /// Unlike the `Parse` implementation, which reads a single expression, this
+    /// reads every top-level form until the input is exhausted. It is the entry
+    /// point used by the interpreter and the CLI.
+    ///
+    /// # Parameters
+    /// - `src`: The BigLisp source code.
+    ///
+    /// # Returns
+    /// - `Ok(Vec<LispExpr>)`: The top-level forms, in source order.
+    /// - `Err(syn::Error)`: If the source cannot be tokenized or parsed.
+    pub fn parse_program(src: &str) -> syn::Result<Vec<LispExpr>> {
+        use syn::parse::Parser;
+
+        let parser = |input: syn::parse::ParseStream| {
+            let mut forms = Vec::new();
+            while !LispExpr::skip_discarded(input)?.is_empty() {
+                forms.push(input.parse::<LispExpr>()?);
+            }
+            Ok(forms)
+        };
+        parser.parse_str(src)
+    }
+
+    /// Returns the source span of this expression.
+    ///
+    /// Symbols, keywords and literals report their own span, while lists and
+    /// vectors report the span of their first element. Operators and empty
+    /// collections carry no position and return `Span::call_site()`.
+    pub fn span(&self) -> Span {
+        match self {
+            LispExpr::Symbol(ident) | LispExpr::Keyword(ident) => ident.span(),
+            LispExpr::Literal(lit) => lit.span(),
+            LispExpr::Char(c) => c.span(),
+            LispExpr::List(exprs) | LispExpr::Vector(exprs) => {
+                exprs.first().map_or_else(Span::call_site, LispExpr::span)
+            }
+            LispExpr::Operator(_) => Span::call_site(),
+        }
+    }
+
+    /// Rebuilds the tree bottom-up, passing every node through `f`.
+    ///
+    /// The children of a list or vector are folded first, so `f` always sees
+    /// a node whose children have already been transformed.
+    pub fn fold<F: FnMut(LispExpr) -> LispExpr>(self, f: &mut F) -> LispExpr {
+        let node = match self {
+            LispExpr::List(exprs) => LispExpr::List(exprs.into_iter().map(|e| e.fold(f)).collect()),
+            LispExpr::Vector(exprs) => {
+                LispExpr::Vector(exprs.into_iter().map(|e| e.fold(f)).collect())
+            }
+            leaf => leaf,
+        };
+        f(node)
+    }
+
+    /// Calls `f` on every node of the tree, parents before their children.
+    pub fn visit<F: FnMut(&LispExpr)>(&self, f: &mut F) {
+        f(self);
+        if let LispExpr::List(exprs) | LispExpr::Vector(exprs) = self {
+            for expr in exprs {
+                expr.visit(f);
+            }
+        }
+    }
+
+    /// Skips any `#_`-discarded forms at the front of `input`.
+    ///
+    /// Like Clojure's `#_` reader macro, `#_form` comments out a single form.
+    /// Rust lexes `#_2` and `#_foo` as `#` followed by the identifier `_2` or
+    /// `_foo`, so an identifier starting with `_` right after `#` is itself the
+    /// discarded form; otherwise `#_` is followed by the form as usual.
+    ///
+    /// # Parameters
+    /// - `input`: The token stream to advance.
+    ///
+    /// # Returns
+    /// - `Ok(input)`: The same stream, positioned at the next form that is kept,
+    ///   so callers can check `is_empty` on the result.
+    /// - `Err(syn::Error)`: If a discarded form cannot be parsed.
+    pub fn skip_discarded<'a, 'b>(
+        input: &'a syn::parse::ParseBuffer<'b>,
+    ) -> syn::Result<&'a syn::parse::ParseBuffer<'b>> {
+        while input.peek(Token![#]) {
+            input.parse::<Token![#]>()?;
+            if input.peek(Token![_]) {
+                input.parse::<Token![_]>()?;
+                input.parse::<LispExpr>()?;
+            } else {
+                let ident = Ident::parse_any(input)?;
+                if !ident.to_string().starts_with('_') {
+                    return Err(syn::Error::new(ident.span(), "expected `#_` before a form"));
+                }
+                LispExpr::parse_hyphenated(&ident, input)?;
+            }
+        }
+        Ok(input)
+    }
+
+    /// Parses the first element of a list.
+    ///
+    /// Hyphenated names at the head of a list are kept as `Operator`s with their
+    /// hyphens intact so `expand_operation` can match forms like `chunk-by`;
+    /// unknown ones fall back to a call of the underscored Rust function.
+    /// `not=` is joined the same way. Lists that start with an operator such as
+    /// `(- a b)` are left untouched.
+    ///
+    /// # Parameters
+    /// - `input`: The token stream positioned at the start of a list's contents.
+    ///
+    /// # Returns
+    /// - `Ok(LispExpr)`: The head expression, joined into an `Operator` if hyphenated.
+    /// - `Err(syn::Error)`: If the head cannot be parsed as a valid `LispExpr`.
+    fn parse_list_head(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // Keyword heads such as `while` in `while-collect` are parsed as symbols
+        // first, so they can be joined like any other identifier.
+        let ident: Ident = if input.peek(Ident) {
+            input.parse()?
+        } else {
+            match input.parse::<LispExpr>()? {
+                LispExpr::Symbol(ident) => ident,
+                other => return Ok(other),
+            }
+        };
+        if ident == "not" && input.peek(Token![=]) && LispExpr::adjacent(ident.span(), input.span())
+        {
+            input.parse::<Token![=]>()?;
+            return Ok(LispExpr::Operator("not=".to_string()));
+        }
+        let name = LispExpr::parse_hyphenated(&ident, input)?;
+        if ident != name {
+            Ok(LispExpr::Operator(name))
+        } else {
+            Ok(LispExpr::Symbol(ident))
+        }
+    }
+
+    /// Joins an identifier with any `-ident` or `->ident` segments that follow it.
+    ///
+    /// `syn` tokenizes `total-count` as `total`, `-`, `count`, so the segments
+    /// are joined only when their spans show no whitespace between them:
+    /// `a-b` is one name while `a - b` and `a -b` stay separate tokens. Where
+    /// the compiler reports no span positions, adjacency can't be shown and
+    /// nothing is joined. Arrow names like `bool->int` are joined the same
+    /// way, and segments may be Rust keywords (`count-if`). A trailing `?` or
+    /// `!` is kept for names such as `contains?` and `swap!`. Threading forms
+    /// listed in `THREADING_FORMS` end at their arrow, so the symbol after
+    /// `cond->` stays a separate argument.
+    ///
+    /// # Parameters
+    /// - `ident`: The identifier that has already been parsed.
+    /// - `input`: The token stream positioned just after `ident`.
+    ///
+    /// # Returns
+    /// - `Ok(String)`: The full name, hyphens included (e.g. `"total-count"`).
+    /// - `Err(syn::Error)`: If the trailing segments cannot be parsed.
+    fn parse_hyphenated(ident: &Ident, input: syn::parse::ParseStream) -> syn::Result<String> {
+        let mut name = ident.to_string();
+        let mut end = ident.span();
+        while LispExpr::adjacent(end, input.span()) {
+            // `->` is two punctuation tokens, so the next segment is third
+            let threading = THREADING_FORMS.contains(&format!("{}->", name).as_str());
+            let separator = if input.peek(Token![->]) {
+                let fork = input.fork();
+                let arrow = fork.parse::<Token![->]>()?;
+                if threading {
+                    // `(cond-> x ...)`: the following identifier is an argument
+                    input.advance_to(&fork);
+                    name.push_str("->");
+                    break;
+                }
+                (fork, arrow.spans[1], "->")
+            } else if input.peek(Token![-]) {
+                let fork = input.fork();
+                let dash = fork.parse::<Token![-]>()?;
+                (fork, dash.spans[0], "-")
+            } else {
+                break;
+            };
+            let (fork, separator_end, separator) = separator;
+            if !fork.peek(Ident::peek_any) || !LispExpr::adjacent(separator_end, fork.span()) {
+                break;
+            }
+            input.advance_to(&fork);
+            let segment = Ident::parse_any(input)?;
+            name.push_str(separator);
+            name.push_str(&segment.to_string());
+            end = segment.span();
+        }
+        if LispExpr::adjacent(end, input.span()) {
+            if input.peek(Token![?]) {
+                input.parse::<Token![?]>()?;
+                name.push('?');
+            } else if input.peek(Token![!]) {
+                input.parse::<Token![!]>()?;
+                name.push('!');
+            }
+        }
+        Ok(name)
+    }
+
+    /// Returns true if `after` starts exactly where `before` ends, with no
+    /// whitespace between them. Unknown positions, reported as line 0, are
+    /// never adjacent.
+    fn adjacent(before: Span, after: Span) -> bool {
+        let end = before.end();
+        end.line != 0 && end == after.start()
+    }
+
+    /// Inserts `value` as the first argument of a threading step.
+    ///
+    /// A list step like `(* 2)` becomes `(* value 2)`, and a bare symbol step
+    /// like `inc` becomes `(inc value)`.
+    fn thread_first(step: &LispExpr, value: &Ident) -> LispExpr {
+        let value = LispExpr::Symbol(value.clone());
+        match step {
+            LispExpr::List(items) if !items.is_empty() => {
+                let mut threaded = vec![items[0].clone(), value];
+                threaded.extend(items[1..].iter().cloned());
+                LispExpr::List(threaded)
+            }
+            other => LispExpr::List(vec![other.clone(), value]),
+        }
+    }
+
+    /// Returns true if this expression is known to produce a string or
+    /// character, so it can be printed with `Display` rather than `{:?}`.
+    fn is_text(&self) -> bool {
+        const TEXT_FORMS: &[&str] = &[
+            "str",
+            "pr-str",
+            "str-repeat",
+            "pad-left",
+            "pad-right",
+            "hex",
+            "bin",
+            "oct",
+            "slurp",
+        ];
+        match self {
+            LispExpr::Literal(Lit::Str(_)) | LispExpr::Char(_) => true,
+            LispExpr::List(items) => match items.first() {
+                Some(LispExpr::Symbol(head)) => TEXT_FORMS.iter().any(|form| head == form),
+                Some(LispExpr::Operator(head)) => TEXT_FORMS.contains(&head.as_str()),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Describes the kind of a literal, or `None` for any other expression.
+    fn literal_kind(&self) -> Option<&'static str> {
+        match self {
+            LispExpr::Literal(Lit::Int(_)) => Some("integer"),
+            LispExpr::Literal(Lit::Float(_)) => Some("float"),
+            LispExpr::Literal(Lit::Str(_)) => Some("string"),
+            LispExpr::Literal(Lit::Bool(_)) => Some("boolean"),
+            LispExpr::Literal(Lit::Char(_)) | LispExpr::Char(_) => Some("character"),
+            _ => None,
+        }
+    }
+
+    /// Checks that the literal branches of `op` are all the same kind of
+    /// literal, so `(if c 1 "two")` gets an error naming both branches rather
+    /// than a type mismatch inside the generated `if`. Non-literal branches are
+    /// left for rustc.
+    fn check_branch_literals<'a>(
+        op: &str,
+        branches: impl IntoIterator<Item = &'a LispExpr>,
+    ) -> Option<TokenStream> {
+        let mut literals = branches
+            .into_iter()
+            .filter_map(|branch| Some((branch, branch.literal_kind()?)));
+        let (first, first_kind) = literals.next()?;
+        let (other, other_kind) = literals.find(|&(_, kind)| kind != first_kind)?;
+        let message = format!(
+            "{} branches must have the same type, but found {} `{}` and {} `{}`",
+            op,
+            first_kind,
+            first.to_rust(),
+            other_kind,
+            other.to_rust()
+        );
+        Some(quote_spanned! { other.span()=> compile_error!(#message) })
+    }
+
+    /// Returns the arguments of a clause like `(finally cleanup)` if this is a
+    /// list headed by the symbol `name`.
+    fn clause_args(&self, name: &str) -> Option<&[LispExpr]> {
+        match self {
+            LispExpr::List(items) => match items.split_first() {
+                Some((LispExpr::Symbol(head), rest)) if head == name => Some(rest),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
     /// Converts a `LispExpr` into a Rust `TokenStream`.
     ///
     /// This method recursively transforms Lisp expressions into equivalent Rust code
@@ -217,7 +871,8 @@ impl LispExpr {
     ///   Rust expression for the given Lisp expression.
     ///
     /// # Expression Type Handling
-    /// - **Symbols**: Direct identifier conversion (e.g., `x` becomes `x`)
+    /// - **Symbols**: Direct identifier conversion (e.g., `x` becomes `x`; a
+    ///   hyphenated `total-count` is parsed as `total_count`)
     /// - **Literals**: Direct literal conversion (e.g., `42` becomes `42`)
     /// - **Operators**: Converted to prefixed identifiers (e.g., `+` becomes `op_plus`)
     /// - **Vectors**: Converted to Rust `vec!` macros with element expansion
@@ -239,6 +894,12 @@ impl LispExpr {
     /// List: (func arg1 arg2)   -> func(arg1, arg2)
     /// ```
     pub fn to_rust(&self) -> TokenStream {
+        with_arities(|| self.expand())
+    }
+
+    /// Converts the expression without collecting arities, for use once
+    /// `with_arities` has set up the table for the whole tree.
+    fn expand(&self) -> TokenStream {
         match self {
             LispExpr::Symbol(ident) => {
                 quote::quote! { #ident }
@@ -246,6 +907,9 @@ impl LispExpr {
             LispExpr::Literal(lit) => {
                 quote::quote! { #lit }
             }
+            LispExpr::Char(c) => {
+                quote::quote! { #c }
+            }
             LispExpr::Operator(op) => {
                 let ident = Ident::new(
                     &format!(
@@ -266,6 +930,15 @@ impl LispExpr {
                 );
                 quote::quote! { #ident }
             }
+            LispExpr::Keyword(name) => {
+                let message = format!("Keyword :{} is only valid inside a special form", name);
+                quote::quote! { compile_error!(#message) }
+            }
+            LispExpr::Vector(_) if !cfg!(feature = "std") => {
+                quote::quote! {
+                    compile_error!("vector literals build a Vec, which needs alloc and is unavailable without the std feature")
+                }
+            }
             LispExpr::Vector(exprs) => {
                 let elements = exprs.iter().map(|e| e.to_rust());
                 quote::quote! { vec![#(#elements),*] }
@@ -278,8 +951,8 @@ impl LispExpr {
                 let rest = &exprs[1..];
 
                 match first {
-                    LispExpr::Symbol(op) => self.expand_operation(&op.to_string(), rest),
-                    LispExpr::Operator(op) => self.expand_operation(op, rest),
+                    LispExpr::Symbol(op) => self.expand_list(&op.to_string(), rest),
+                    LispExpr::Operator(op) => self.expand_list(op, rest),
                     _ => {
                         let funct = first.to_rust();
                         let args = rest.iter().map(|e| e.to_rust());
@@ -292,6 +965,197 @@ impl LispExpr {
         }
     }
 
+    /// Expands a list headed by the operation `op`.
+    ///
+    /// `let`, `defn`, `fn`, `closure` and `as->` record their own bindings
+    /// for `call`'s arity check. Any other form runs in a scope where the
+    /// symbols in its vector arguments, such as the loop variable of
+    /// `doseq`, hide outer definitions of the same name.
+    fn expand_list(&self, op: &str, args: &[LispExpr]) -> TokenStream {
+        match op {
+            "let" | "defn" | "fn" | "closure" | "as->" => self.expand_operation(op, args),
+            _ => scoped(|| {
+                shadow_vector_symbols(args, false);
+                self.expand_operation(op, args)
+            }),
+        }
+    }
+
+    /// Converts a sequence of forms into a Rust block returning the last value.
+    ///
+    /// This backs `do` and multi-form `lisp!` invocations. A `(def name value)`
+    /// form becomes a `let` statement, so later forms in the sequence can refer
+    /// to `name`; as the last form it also yields the value.
+    ///
+    /// # Parameters
+    /// - `forms`: The forms to evaluate in order.
+    ///
+    /// # Returns
+    /// - `TokenStream`: A block expression; `()` if `forms` is empty.
+    ///
+    /// # Example Transformation
+    /// ```text
+    /// (def a 1) (def b 2) (+ a b)  ->  { let a = 1; let b = 2; a + b }
+    /// ```
+    pub fn to_rust_block(forms: &[LispExpr]) -> TokenStream {
+        with_arities(|| scoped(|| LispExpr::expand_block(forms)))
+    }
+
+    /// The body of `to_rust_block`, run inside `with_arities`.
+    fn expand_block(forms: &[LispExpr]) -> TokenStream {
+        let mut statements = Vec::new();
+        for (i, form) in forms.iter().enumerate() {
+            let is_last = i + 1 == forms.len();
+            match form.clause_args("def") {
+                Some(args) => {
+                    statements.push(LispExpr::def_statement(args));
+                    if let [LispExpr::Symbol(name), value] = args {
+                        bind_arity(name, Some(value));
+                        if is_last {
+                            statements.push(quote! { #name });
+                        }
+                    }
+                }
+                None => {
+                    let expr = form.to_rust();
+                    if is_last {
+                        statements.push(expr);
+                    } else {
+                        statements.push(quote! { #expr; });
+                    }
+                }
+            }
+        }
+        quote! { { #(#statements)* } }
+    }
+
+    /// Converts a sequence of forms into bare Rust statements.
+    ///
+    /// Unlike `to_rust_block`, the result is not wrapped in a block and yields
+    /// no value: every form becomes a statement, and the forms of a top-level
+    /// `(do ...)` are spliced in place. A `(def name value)` becomes a `let`
+    /// that stays in scope for whatever follows the statements. This backs the
+    /// `lisp_block!` macro.
+    ///
+    /// # Example Transformation
+    /// ```text
+    /// (def a 1) (do (def b 2) (println b))  ->  let a = 1; let b = 2; println!("{:?}", b);
+    /// ```
+    pub fn to_rust_statements(forms: &[LispExpr]) -> TokenStream {
+        with_arities(|| {
+            let mut statements = Vec::new();
+            LispExpr::push_statements(forms, &mut statements);
+            quote! { #(#statements)* }
+        })
+    }
+
+    /// Appends one statement per form to `statements`, flattening `do` forms.
+    fn push_statements(forms: &[LispExpr], statements: &mut Vec<TokenStream>) {
+        for form in forms {
+            if let Some(args) = form.clause_args("def") {
+                statements.push(LispExpr::def_statement(args));
+                if let [LispExpr::Symbol(name), value] = args {
+                    bind_arity(name, Some(value));
+                }
+            } else if let Some(body) = form
+                .clause_args("do")
+                .filter(|body| !matches!(body.first(), Some(LispExpr::Keyword(_))))
+            {
+                LispExpr::push_statements(body, statements);
+            } else {
+                let expr = form.to_rust();
+                statements.push(quote! { #expr; });
+            }
+        }
+    }
+
+    /// The `let` statement for the arguments of a `(def name value)` form.
+    fn def_statement(args: &[LispExpr]) -> TokenStream {
+        match args {
+            [LispExpr::Symbol(name), value] => {
+                let value = value.to_rust();
+                quote! { let #name = #value; }
+            }
+            _ => quote! { compile_error!("Definition format: (def name value)"); },
+        }
+    }
+
+    /// Converts a top-level `defn` or `def` form into a Rust item.
+    ///
+    /// Where `to_rust` turns `defn` into a closure expression, this produces a
+    /// named `fn` that can be declared at module level and call itself
+    /// recursively. A docstring after the parameter vector becomes the
+    /// function's doc comment. `(def NAME value)` becomes a `const` whose type
+    /// follows a literal value (a suffix, `f64` for floats, `bool`, or
+    /// `&'static str`) and is `i32` otherwise. This backs the `lisp_item!` macro.
+    ///
+    /// A multi-arity `(defn f ([x] ...) ([x y] ...))` becomes one function per
+    /// arity, named with its parameter count (`f_1`, `f_2`). Within the bodies,
+    /// calls like `(f x 0)` are routed to the matching arity.
+    ///
+    /// # Returns
+    /// - `TokenStream`: The item, or a `compile_error!` if the form is not a
+    ///   well-formed `defn` or `def`.
+    ///
+    /// # Example Transformation
+    /// ```text
+    /// (defn sq [x] "Squares x." (* x x))
+    ///   -> #[doc = "Squares x."] fn sq(x: i32) -> i32 { x * x }
+    /// (def RATE 1.5)  ->  const RATE: f64 = 1.5;
+    /// (defn f ([x] (f x 1)) ([x y] (* x y)))
+    ///   -> fn f_1(x: i32) -> i32 { f_2(x, 1) } fn f_2(x: i32, y: i32) -> i32 { x * y }
+    /// ```
+    pub fn to_rust_item(&self) -> TokenStream {
+        if let Some(args) = self.clause_args("defn") {
+            if let Some(arities) = Defn::parse_arities(args) {
+                return Defn::to_rust_arities(&arities);
+            }
+            match Defn::parse(args) {
+                Ok(Defn {
+                    name,
+                    params,
+                    doc,
+                    body,
+                }) => {
+                    let body_tokens = body.to_rust();
+                    let doc = doc.map(|doc| quote! { #[doc = #doc] });
+                    quote! {
+                        #doc
+                        fn #name(#(#params: i32),*) -> i32 {
+                            #body_tokens
+                        }
+                    }
+                }
+                Err(error) => error,
+            }
+        } else if let Some(args) = self.clause_args("def") {
+            match args {
+                [LispExpr::Symbol(name), value] => {
+                    let ty = match value {
+                        LispExpr::Literal(Lit::Int(lit)) if !lit.suffix().is_empty() => {
+                            let suffix = Ident::new(lit.suffix(), Span::call_site());
+                            quote! { #suffix }
+                        }
+                        LispExpr::Literal(Lit::Float(lit)) if !lit.suffix().is_empty() => {
+                            let suffix = Ident::new(lit.suffix(), Span::call_site());
+                            quote! { #suffix }
+                        }
+                        LispExpr::Literal(Lit::Float(_)) => quote! { f64 },
+                        LispExpr::Literal(Lit::Bool(_)) => quote! { bool },
+                        LispExpr::Literal(Lit::Str(_)) => quote! { &'static str },
+                        LispExpr::Char(_) => quote! { char },
+                        _ => quote! { i32 },
+                    };
+                    let value = value.to_rust();
+                    quote! { const #name: #ty = #value; }
+                }
+                _ => quote! { compile_error!("Definition format: (def name value)") },
+            }
+        } else {
+            quote! { compile_error!("lisp_item! expects a (defn ...) or (def ...) form") }
+        }
+    }
+
     /// Expands BigLisp operations into equivalent Rust code.
     ///
     /// This method is the core of the BigLisp DSL, transforming Lisp-style function calls
@@ -307,6 +1171,9 @@ impl LispExpr {
     ///
     /// # Supported Operations
     ///
+    /// Every form listed here is also registered in [`builtins()`], which
+    /// tools use to generate help text; keep the two in step.
+    ///
     /// ## Arithmetic Operations
     /// - `+`: Addition with identity element 0, supports single argument
     /// - `-`: Subtraction and unary negation
@@ -318,63 +1185,253 @@ impl LispExpr {
     /// - `=`/`eq`: Equality comparison
     /// - `<`, `>`: Less than, greater than
     /// - `gte`, `lte`: Greater/less than or equal
-    /// - `ne`: Not equal
+    /// - `ne`/`not=`: Not equal
     ///
     /// ## Boolean Operations
-    /// - `and`: Logical AND (requires at least 2 arguments)
-    /// - `or`: Logical OR (requires at least 2 arguments)
+    /// - `and`: Logical AND; `(and)` is `true`
+    /// - `or`: Logical OR; `(or)` is `false`
     /// - `not`: Logical NOT (exactly 1 argument)
+    /// - `xor`: Exclusive OR of two booleans
+    /// - `nand`, `nor`: Negated `and`/`or`; `(nand)` is `false`, `(nor)` is `true`
+    /// - `or-val`: First truthy operand, or the last operand if none are truthy
+    /// - `and-val`: First falsy operand, or the last operand if all are truthy
+    ///
+    /// For `or-val`/`and-val` a value is falsy when it equals its type's
+    /// `Default` (`0`, `false`, `""`, an empty vector) and truthy otherwise.
+    /// All operands must share a type.
     ///
     /// ## Control Flow
-    /// - `if`: Conditional with optional else branch
+    /// - `if`: Conditional with optional else branch. Literal branches of
+    ///   `if`, `case` and `condp` must be the same kind of literal, so
+    ///   `(if c 1 "two")` is a compile error naming both
+    /// - `if-first`: `(if-first [x coll] then else)` binds `x` to the first
+    ///   element and evaluates `then`, or evaluates `else` if `coll` is empty;
+    ///   `when-first` takes no else branch and returns `()`
     /// - `let`: Local variable bindings with vector syntax
-    /// - `do`: Sequential execution block
-    /// - `while`: While loop with condition and body
+    /// - `letfn`: Local `fn` items that may call each other recursively; like
+    ///   any `fn` item they cannot capture surrounding variables. Their
+    ///   parameters and return value are `i32`; `(name [params] :as bool body)`
+    ///   gives a different return type
+    /// - `do`: Sequential execution block; `(def name value)` forms inside it bind
+    ///   `name` for the rest of the block. `(do :as i64 ...)` gives the block's
+    ///   value an explicit type, for when inference has nothing to go on
+    /// - `while`: While loop with condition and body, run for side effects; returns `()`
+    /// - `while-collect`: While loop returning a vector of each iteration's body value
     /// - `dotimes`: For-like loop with variable, count, and body
+    /// - `dotimes-sum`: Like `dotimes`, but returns the sum of the body values
+    /// - `doseq`: Side-effecting iteration over the elements of a collection
+    /// - `doseq-indexed`: Like `doseq`, with `[i x coll]` also binding each
+    ///   element's `i32` index
+    /// - `for`: List comprehension with an optional `:when` guard
+    /// - `case`: Match a value against literal keys, with an optional trailing default.
+    ///   With string keys the subject may be a `String` or `&str`
+    /// - `condp`: `(condp pred subject a result-a ... default?)` returns the result
+    ///   for the first test value where `pred(subject, a)` holds; the predicate
+    ///   may be an operator such as `=` or a two-argument function. Without a
+    ///   default, panics with "condp: no clause matched" if no test holds
+    /// - `cond->`: Thread a value through each step whose condition holds, as the
+    ///   step's first argument
+    /// - `as->`: Thread a value through each step under a name, so each step
+    ///   places it wherever it likes
+    /// - `some->`: Thread an `Option` through steps that each return an
+    ///   `Option`, passing the unwrapped value as the first argument; the first
+    ///   `None` short-circuits the rest
+    /// - `doto`: Call each step with the value as its first argument for side
+    ///   effects, then return the value itself
     ///
     /// ## Function Operations
-    /// - `defn`: Function definition creating closures
-    /// - `call`: Function invocation
+    /// - `defn`: Function definition creating closures over `i32` parameters
+    ///   whose return type is inferred from the body, so predicates return
+    ///   `bool`; an optional docstring after the parameter vector is ignored
+    ///   here and becomes a doc comment with `lisp_item!`. Multi-arity
+    ///   definitions are rejected here; use `lisp_item!`, which generates one
+    ///   function per arity
+    /// - `fn`: Anonymous function, e.g. `(fn [x] (* x 2))` or the thunk
+    ///   `(fn [] (next-id))`; parameters are `i32` like `defn`
+    /// - `closure`: `(closure [state 10] [x] body)` binds `state` like `let` and
+    ///   returns a `move` closure of `x` that captures it by value
+    /// - `call`: Function invocation; calling a name bound by `let` or `def`
+    ///   to a `defn` in an enclosing scope with the wrong number of arguments
+    ///   is a compile error. Rebinding the name, including as a parameter or
+    ///   loop variable, hides that `defn` from the check
+    /// - `identity`: Returns its argument unchanged
+    /// - `constantly`: Closure that ignores its argument and returns a fixed value
+    /// - `every-pred`, `any-pred`: Closure testing whether all/any of the
+    ///   predicates hold for its argument; predicates may be builtins such as
+    ///   `pos` or function values, which are evaluated once when the closure
+    ///   is built, and testing short-circuits
+    /// - `juxt`: Closure applying each function to its argument, returning the
+    ///   results as a tuple: `(juxt f g)` is `|x| (f(x), g(x))`
+    /// - `memoize`: Wraps a single `i32` argument function in a closure that caches its results
     ///
     /// ## Data Structure Operations
     /// - `first`: Get first element of collection
     /// - `rest`: Get all but first element
+    /// - `last`: Get last element of collection
+    /// - `butlast`: Get all but last element
+    /// - `second`, `third`: Get the second/third element, or the default value
+    ///   if the collection is too short (like `first`)
+    /// - `nthrest`: All but the first `n` elements; empty if there are fewer,
+    ///   and the whole collection for a negative `n`
+    /// - `take-nth`: Every `n`th element, starting with the first; a literal
+    ///   `0` step is a compile error and a computed step of 0 or less panics
+    /// - `split-at`: `(first n elements, the rest)` as a tuple of vectors; `n`
+    ///   is clamped to the collection's length
+    /// - `slice`: The elements from `start` up to (not including) `end`; both
+    ///   bounds are clamped to the collection, so out-of-range slices shrink
+    /// - `list`: Build a vector from its arguments
+    /// - `vec-of`: Build a vector with an explicit element type, e.g.
+    ///   `(vec-of i64)` for an empty `Vec<i64>` or `(vec-of u8 1 2)`
     /// - `cons`: Prepend element to collection
     /// - `count`: Get collection length
+    /// - `char-at`: The `i`th character of a string. Indexing counts Unicode
+    ///   characters rather than bytes, so it walks the string from the start;
+    ///   panics if `i` is out of range
+    /// - `ascii-code`: Character code of a `char`, as `i32`
+    /// - `code->char`: The `char` for a code in `0..=255`; larger codes are
+    ///   truncated to their low byte
+    /// - `contains?`: Test whether a vector contains a value
+    /// - `has-key?`: Test whether a map contains a key
+    /// - `select-keys`: New `HashMap` with only the listed keys that are present
+    /// - `sorted-pairs`: The `(key, value)` entries of a map as a vector sorted
+    ///   by key, for deterministic output
+    /// - `merge`: Copy of the first map extended with the rest; later maps
+    ///   override earlier keys
+    /// - `not-empty`: `Some(coll)` if the collection has elements, otherwise `None`
+    /// - `repeat`: Vector of `n` copies of a value; empty for a negative `n`
+    /// - `assoc-in`: Copy of a nested vector with the element at a literal index
+    ///   path (e.g. `[1 0]`) replaced
+    /// - `assoc-vec`: Copy of a vector with index `i` set to `x`; panics with the
+    ///   index and length if `i` is out of bounds
+    /// - `update`: Copy of a vector with `f` applied to the element at index `i`
+    /// - `vec->array`: Convert a vector to a `[T; N]` array for a literal `N`;
+    ///   panics if the vector does not have exactly `N` elements
+    /// - `make-grid`: `rows` x `cols` vector of vectors filled with a value;
+    ///   negative dimensions clamp to 0
+    /// - `grid-ref`: Element at row `r`, column `c` of a grid; panics naming
+    ///   the index if either is negative
+    /// - `chunk-by`: Split a collection into runs of elements sharing the same key
+    /// - `group-by`: `HashMap` from each key to the elements that produced it, in order
+    /// - `most-common`: The `n` most frequent elements as `(element, count)` pairs,
+    ///   sorted by descending count; ties keep first-occurrence order
+    /// - `reductions`: Every intermediate accumulator of a fold, starting with `init`
+    /// - `reduce-while`: `(reduce-while pred f init coll)` folds like `reduce`,
+    ///   but checks `pred(acc)` before each element and stops, returning `acc`,
+    ///   as soon as it fails
+    /// - `iterate`: The first `n` values of `x`, `f(x)`, `f(f(x))`, ...
+    /// - `mapcat`: Apply a collection-returning function to each element and
+    ///   concatenate the results into one vector
+    /// - `keep`: Apply an `Option`-returning function to each element, keeping
+    ///   the `Some` values and dropping the `None`s
+    /// - `repeatedly`: Vector of the results of calling the zero-argument
+    ///   function `f` `n` times; a negative `n` calls it zero times
+    /// - `max-by`, `min-by`: `Some(element)` with the largest/smallest key, or
+    ///   `None` for an empty collection; ties pick the last/first such element
+    /// - `min-max`: `(min, max)` of a collection in a single pass; panics if the
+    ///   collection is empty
+    /// - `find-index`: `Some(i)` for the first element satisfying a predicate,
+    ///   as `i32`, or `None` if no element matches
+    ///
+    /// ## Mutable State
+    /// - `atom`: Wrap a value in a `RefCell`
+    /// - `deref`: Copy the current value out of an atom
+    /// - `swap!`: Replace an atom's value with `f` applied to it; returns `()`
+    /// - `inc!`, `dec!`: Add/subtract one from a mutable variable in place;
+    ///   returns `()`
     ///
     /// ## String Operations
     /// - `str`: String concatenation of multiple arguments
+    /// - `str-repeat`: A string repeated `n` times; empty for a negative `n`
+    /// - `hex`, `bin`, `oct`: A number formatted in base 16/2/8 with its `0x`,
+    ///   `0b` or `0o` prefix
+    /// - `pad-left`, `pad-right`: Display form of a value padded with spaces to
+    ///   at least `width` characters, aligned right/left
     ///
     /// ## Math Utility Functions
     /// - `min`, `max`: Minimum/maximum of multiple values
-    /// - `abs`: Absolute value
+    /// - `gcd`, `lcm`: Greatest common divisor/least common multiple of multiple values
+    /// - `abs`: Absolute value, keeping the argument's numeric type
     /// - `inc`, `dec`: Increment/decrement by 1
+    /// - `bool->int`: Convert a boolean to `1` or `0`
+    /// - `count-true`: Number of `true` elements in a collection of booleans
+    /// - `count-if`: Number of elements satisfying a predicate, as `i32`
     ///
     /// ## Predicate Functions
     /// - `zero`: Test if value equals zero
     /// - `pos`, `neg`: Test if value is positive/negative
     /// - `even`, `odd`: Test if value is even/odd
+    /// - `between?`: Test if `lo <= x <= hi`
+    /// - `in-range?`: Test if `lo <= x < hi`
+    /// - `truthy?`, `falsy?`: Lisp-style truthiness, where `0`, the empty
+    ///   string and the empty vector are falsy and everything else is truthy.
+    ///   Only integers are supported for now: `(truthy? x)` is `x != 0`
     ///
     /// ## Error Handling
-    /// - `try`: Panic-safe execution with optional fallback
+    /// - `try`: Panic-safe execution with an optional fallback, given either
+    ///   directly or as `(catch handler)`, and an optional `(finally cleanup)`
+    ///   that always runs; when a fallback is given, the caught panic's message
+    ///   is not printed
+    /// - `try-verbose`: Like `try`, but keeps the default panic hook output
+    /// - `throw`: Panic with a message; usable wherever a value of any type is expected
     ///
     /// ## Variable Capture
-    /// - `with-vars`: Capture external variables in scope
+    /// - `with-vars`: Capture external variables in scope; `[x as base]` binds
+    ///   the variable `x` under the name `base`
+    ///
+    /// ## Environment
+    /// - `env`: Read an environment variable as `Option<String>`, or as a `String`
+    ///   with a fallback when a default is given
+    /// - `read-line`: Read one line from stdin as a trimmed `String`; panics if
+    ///   stdin cannot be read
+    /// - `slurp`: Read a whole file into a `String`; panics with the path if it
+    ///   cannot be read
+    /// - `spit`: Write a string to a file, replacing its contents; panics with
+    ///   the path if it cannot be written
+    /// - `try-slurp`, `try-spit`: Like `slurp`/`spit`, but return the
+    ///   `std::io::Result` instead of panicking
     ///
     /// ## Debug Operations
-    /// - `println`: Debug printing
+    /// - `println`: Print a value. Strings and characters, whether literal or
+    ///   built by a string form such as `str`, use `Display`; everything else
+    ///   uses `{:?}`
+    /// - `pr-str`: The `{:?}` debug form of a value as a `String`
+    /// - `print-table`: Print each element of a collection on its own numbered line
+    /// - `time`: Evaluate an expression, print the elapsed time to stderr, and
+    ///   return its value
     ///
     /// # Examples
     /// ```ignore
-    /// // Arithmetic: (+ 1 2 3) -> 0 + 1 + 2 + 3
+    /// // Arithmetic: (+ 1 2 3) -> (1) + (2) + (3)
     /// // Control flow: (if (> x 0) "pos" "neg") -> if x > 0 { "pos" } else { "neg" }
-    /// // Functions: (defn sq [x] (* x x)) -> closure |x: i32| -> i32 { x * x }
+    /// // Functions: (defn sq [x] (* x x)) -> closure |x: i32| { x * x }
     /// ```
     ///
+    /// # `no_std`
+    /// Expansions name only `core` paths where they can. With the default
+    /// `std` feature disabled, the forms in `STD_FORMS` (`try`, `str`, the
+    /// I/O forms and the `HashMap`-based forms) are rejected at compile time,
+    /// as are vector literals and the `Vec`-building forms in `ALLOC_FORMS`.
+    /// Arithmetic, comparison and control flow are unaffected.
+    ///
     /// # Error Handling
     /// Invalid argument counts or malformed expressions generate compile-time errors
     /// using `compile_error!` macro, ensuring type safety and preventing runtime errors.
     fn expand_operation(&self, op_str: &str, args: &[LispExpr]) -> TokenStream {
+        if !cfg!(feature = "std") && STD_FORMS.contains(&op_str) {
+            let message = format!(
+                "{} requires std and is unavailable without the std feature",
+                op_str
+            );
+            return quote! { compile_error!(#message) };
+        }
+        if !cfg!(feature = "std") && ALLOC_FORMS.contains(&op_str) {
+            let message = format!(
+                "{} builds a Vec, which needs alloc and is unavailable without the std feature",
+                op_str
+            );
+            return quote! { compile_error!(#message) };
+        }
+
         match op_str {
             // Arithmetic Operations
             "+" => {
@@ -383,12 +1440,7 @@ impl LispExpr {
                 } else if args.len() == 1 {
                     args[0].to_rust()
                 } else {
-                    let terms = args.iter().map(|e| e.to_rust());
-                    let mut result = quote! { 0 };
-                    for term in terms {
-                        result = quote! { #result + (#term) };
-                    }
-                    result
+                    infix_chain(args, quote! { + })
                 }
             }
             "-" => {
@@ -396,13 +1448,7 @@ impl LispExpr {
                     let arg = args[0].to_rust();
                     quote! { -(#arg) }
                 } else if args.len() >= 2 {
-                    let first = args[0].to_rust();
-                    let rest = args[1..].iter().map(|e| e.to_rust());
-                    let mut result = quote! { (#first) };
-                    for term in rest {
-                        result = quote! { #result - (#term) };
-                    }
-                    result
+                    infix_chain(args, quote! { - })
                 } else {
                     quote! { compile_error!("Subtraction requires at least 1 argument") }
                 }
@@ -413,23 +1459,12 @@ impl LispExpr {
                 } else if args.len() == 1 {
                     args[0].to_rust()
                 } else {
-                    let terms = args.iter().map(|e| e.to_rust());
-                    let mut result = quote! { 1 };
-                    for term in terms {
-                        result = quote! { #result * (#term) };
-                    }
-                    result
+                    infix_chain(args, quote! { * })
                 }
             }
             "/" => {
                 if args.len() >= 2 {
-                    let first = args[0].to_rust();
-                    let rest = args[1..].iter().map(|e| e.to_rust());
-                    let mut result = quote! { (#first) };
-                    for term in rest {
-                        result = quote! { #result / (#term) };
-                    }
-                    result
+                    infix_chain(args, quote! { / })
                 } else {
                     quote! { compile_error!("Division requires at least 2 arguments") }
                 }
@@ -480,7 +1515,7 @@ impl LispExpr {
                     quote! { compile_error!("Less-than-or-equal requires exactly 2 arguments") }
                 }
             }
-            "ne" => {
+            "ne" | "not=" => {
                 if args.len() == 2 {
                     let left = args[0].to_rust();
                     let right = args[1].to_rust();
@@ -506,6 +1541,9 @@ impl LispExpr {
                     quote! { if (#cond) { #then_branch } }
                 }
                 3 => {
+                    if let Some(error) = LispExpr::check_branch_literals("if", &args[1..]) {
+                        return error;
+                    }
                     let cond = args[0].to_rust();
                     let then_branch = args[1].to_rust();
                     let else_branch = args[2].to_rust();
@@ -513,24 +1551,51 @@ impl LispExpr {
                 }
                 _ => quote! { compile_error!("If requires 2 or 3 arguments") },
             },
+            "if-first" | "when-first" => {
+                let arity = if op_str == "if-first" { 3 } else { 2 };
+                let [LispExpr::Vector(binding), then_branch, else_branch @ ..] = args else {
+                    let message = format!("{} requires a [name coll] binding and a body", op_str);
+                    return quote! { compile_error!(#message) };
+                };
+                let [LispExpr::Symbol(name), coll] = binding.as_slice() else {
+                    let message = format!("{} binding must be [name coll]", op_str);
+                    return quote! { compile_error!(#message) };
+                };
+                let coll = coll.to_rust();
+                let then_branch = then_branch.to_rust();
+                match else_branch {
+                    _ if args.len() != arity => {
+                        let message = format!("{} requires exactly {} arguments", op_str, arity);
+                        quote! { compile_error!(#message) }
+                    }
+                    [else_branch] => {
+                        let else_branch = else_branch.to_rust();
+                        quote! {
+                            match (#coll).first() {
+                                Some(&#name) => #then_branch,
+                                None => #else_branch,
+                            }
+                        }
+                    }
+                    _ => quote! { if let Some(&#name) = (#coll).first() { #then_branch } },
+                }
+            }
             // Let bindings
             "let" => {
                 if args.len() >= 2 {
                     if let LispExpr::Vector(bindings) = &args[0] {
-                        let body = args[1].to_rust();
-                        let mut lets = TokenStream::new();
-
-                        for binding in bindings.chunks(2) {
-                            if binding.len() == 2 {
-                                if let (LispExpr::Symbol(name), value) = (&binding[0], &binding[1])
-                                {
+                        scoped(|| {
+                            let mut lets = TokenStream::new();
+                            for binding in bindings.chunks(2) {
+                                if let [LispExpr::Symbol(name), value] = binding {
                                     let value_tokens = value.to_rust();
+                                    bind_arity(name, Some(value));
                                     lets.extend(quote! { let #name = #value_tokens; });
                                 }
                             }
-                        }
-
-                        quote! { { #lets #body } }
+                            let body = args[1].to_rust();
+                            quote! { { #lets #body } }
+                        })
                     } else {
                         quote! { compile_error!("Let requires vector of bindings") }
                     }
@@ -539,43 +1604,168 @@ impl LispExpr {
                 }
             }
 
-            // Function definition - now creates a closure that can be called
-            "defn" => {
-                if args.len() >= 3 {
-                    if let (LispExpr::Symbol(name), LispExpr::Vector(params), body) =
-                        (&args[0], &args[1], &args[2])
-                    {
-                        let param_names: Vec<_> = params
-                            .iter()
-                            .filter_map(|p| {
-                                if let LispExpr::Symbol(s) = p {
-                                    Some(s)
-                                } else {
-                                    None
+            // Local, possibly mutually recursive, functions
+            "letfn" => {
+                if args.len() == 2 {
+                    let LispExpr::Vector(specs) = &args[0] else {
+                        return quote! { compile_error!("letfn requires a vector of function specs") };
+                    };
+                    let mut fns = Vec::new();
+                    for spec in specs {
+                        // Heads like `even?` are parsed as operators, so they
+                        // are mangled the same way their call sites are.
+                        let name = match spec {
+                            LispExpr::List(items) => match items.first() {
+                                Some(LispExpr::Symbol(name)) => Some(name.clone()),
+                                Some(LispExpr::Operator(name))
+                                    if name.starts_with(|c: char| c.is_alphabetic()) =>
+                                {
+                                    Some(Ident::new(&rust_name(name), Span::call_site()))
                                 }
-                            })
-                            .collect();
-                        let body_tokens = body.to_rust();
+                                _ => None,
+                            },
+                            _ => None,
+                        };
+                        let (Some(name), LispExpr::List(items)) = (name, spec) else {
+                            return quote! {
+                                compile_error!("letfn function spec format: (name [params] body)")
+                            };
+                        };
+                        let mut parts = vec![LispExpr::Symbol(name)];
+                        parts.extend(items[1..].iter().cloned());
+                        // `fn` items can't infer their return type, so it is
+                        // `i32` unless given with `:as` after the parameters.
+                        let ret = match parts.as_slice() {
+                            [_, _, LispExpr::Keyword(kw), LispExpr::Symbol(ty), _, ..]
+                                if kw == "as" =>
+                            {
+                                let ty = ty.clone();
+                                parts.drain(2..4);
+                                quote! { #ty }
+                            }
+                            [_, _, LispExpr::Keyword(kw), ..] if kw == "as" => {
+                                return quote! { compile_error!("letfn :as requires a type name and a body") };
+                            }
+                            _ => quote! { i32 },
+                        };
+                        match Defn::parse(&parts) {
+                            Ok(Defn {
+                                name, params, body, ..
+                            }) => {
+                                let body_tokens = body.to_rust();
+                                fns.push(quote! {
+                                    fn #name(#(#params: i32),*) -> #ret {
+                                        #body_tokens
+                                    }
+                                });
+                            }
+                            Err(error) => return error,
+                        }
+                    }
+                    let body = args[1].to_rust();
+                    quote! {
+                        {
+                            #(#fns)*
+                            #body
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("letfn requires function specs and a body") }
+                }
+            }
+
+            // Function definition - now creates a closure that can be called
+            "defn" if Defn::parse_arities(args).is_some() => {
+                quote! { compile_error!("multi-arity defn is only supported by lisp_item!") }
+            }
+            "defn" => match Defn::parse(args) {
+                Ok(Defn {
+                    name, params, body, ..
+                }) => {
+                    let body_tokens = scoped(|| {
+                        for param in &params {
+                            bind_arity(param, None);
+                        }
+                        body.to_rust()
+                    });
+                    quote! {
+                        {
+                            let #name = |#(#params: i32),*| {
+                                #body_tokens
+                            };
+                            #name
+                        }
+                    }
+                }
+                Err(error) => error,
+            },
+
+            "fn" => match args {
+                [LispExpr::Vector(params), body] => {
+                    let params = params.iter().map(|param| match param {
+                        LispExpr::Symbol(name) => quote! { #name: i32 },
+                        _ => quote! { compile_error!("fn parameters must be symbols") },
+                    });
+                    let body = scoped(|| {
+                        shadow_vector_symbols(&args[..1], false);
+                        body.to_rust()
+                    });
+                    quote! { |#(#params),*| { #body } }
+                }
+                _ => quote! { compile_error!("fn requires a parameter vector and a body") },
+            },
 
+            "closure" => {
+                if let [LispExpr::Vector(bindings), LispExpr::Vector(params), body] = args {
+                    scoped(|| {
+                        let mut lets = TokenStream::new();
+                        for binding in bindings.chunks(2) {
+                            match binding {
+                                [LispExpr::Symbol(name), value] => {
+                                    let value_tokens = value.to_rust();
+                                    bind_arity(name, Some(value));
+                                    lets.extend(quote! { let #name = #value_tokens; });
+                                }
+                                _ => {
+                                    return quote! { compile_error!("closure bindings must be name/value pairs") };
+                                }
+                            }
+                        }
+                        let params = params.iter().map(|param| match param {
+                            LispExpr::Symbol(name) => quote! { #name: i32 },
+                            _ => quote! { compile_error!("closure parameters must be symbols") },
+                        });
+                        shadow_vector_symbols(&args[1..2], false);
+                        let body = body.to_rust();
                         quote! {
                             {
-                                let #name = |#(#param_names: i32),*| -> i32 {
-                                    #body_tokens
-                                };
-                                #name
+                                #lets
+                                move |#(#params),*| { #body }
                             }
                         }
-                    } else {
-                        quote! { compile_error!("Function definition format: (defn name [params] body)") }
-                    }
+                    })
                 } else {
-                    quote! { compile_error!("Function definition requires name, params, and body") }
+                    quote! { compile_error!("closure requires a binding vector, a parameter vector and a body") }
                 }
             }
 
             // Function call
             "call" => {
-                if args.len() >= 1 {
+                if !args.is_empty() {
+                    if let LispExpr::Symbol(name) = &args[0] {
+                        let expected = arity_of(name);
+                        let given = args.len() - 1;
+                        if let Some(expected) = expected.filter(|&expected| expected != given) {
+                            let message = format!(
+                                "`{}` takes {} argument{} but was called with {}",
+                                name,
+                                expected,
+                                if expected == 1 { "" } else { "s" },
+                                given
+                            );
+                            return quote_spanned! { name.span()=> compile_error!(#message) };
+                        }
+                    }
                     let func = args[0].to_rust();
                     let func_args = args[1..].iter().map(|e| e.to_rust());
                     quote! { (#func)(#(#func_args),*) }
@@ -583,45 +1773,249 @@ impl LispExpr {
                     quote! { compile_error!("call requires at least a function") }
                 }
             }
+            "identity" => {
+                if args.len() == 1 {
+                    args[0].to_rust()
+                } else {
+                    quote! { compile_error!("identity requires exactly 1 argument") }
+                }
+            }
+            "constantly" => {
+                if args.len() == 1 {
+                    let value = args[0].to_rust();
+                    let v = gensym("v");
+                    quote! { { let #v = #value; move |_| #v.clone() } }
+                } else {
+                    quote! { compile_error!("constantly requires exactly 1 argument") }
+                }
+            }
+            "every-pred" | "any-pred" => {
+                if !args.is_empty() {
+                    let x = gensym("x");
+                    let mut lets = Vec::new();
+                    // Builtin predicates like `even` are forms rather than
+                    // values, so they are applied as `(pred x)`. Anything else
+                    // is evaluated once, like `juxt` does, and then called.
+                    let tests: Vec<_> = args
+                        .iter()
+                        .map(|pred| {
+                            let builtin = match pred {
+                                LispExpr::Symbol(name) => Some(name.to_string()),
+                                LispExpr::Operator(name) => Some(name.clone()),
+                                _ => None,
+                            }
+                            .is_some_and(|name| builtins().iter().any(|b| b.name == name));
+                            if builtin {
+                                let x = LispExpr::Symbol(x.clone());
+                                LispExpr::List(vec![pred.clone(), x]).to_rust()
+                            } else {
+                                let f = gensym("pred");
+                                let value = pred.to_rust();
+                                lets.push(quote! { let #f = #value; });
+                                quote! { #f(#x) }
+                            }
+                        })
+                        .collect();
+                    let combined = if op_str == "every-pred" {
+                        quote! { #((#tests))&&* }
+                    } else {
+                        quote! { #((#tests))||* }
+                    };
+                    quote! {
+                        {
+                            #(#lets)*
+                            move |#x| #combined
+                        }
+                    }
+                } else {
+                    let message = format!("{} requires at least 1 predicate", op_str);
+                    quote! { compile_error!(#message) }
+                }
+            }
+            "juxt" => {
+                if !args.is_empty() {
+                    let funcs: Vec<_> = args.iter().map(|_| gensym("f")).collect();
+                    let values = args.iter().map(|f| f.to_rust());
+                    let x = gensym("x");
+                    quote! {
+                        {
+                            #(let #funcs = #values;)*
+                            move |#x| (#(#funcs(#x),)*)
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("juxt requires at least 1 function") }
+                }
+            }
+            "memoize" => {
+                if args.len() == 1 {
+                    let func = args[0].to_rust();
+                    let f = gensym("f");
+                    let cache = gensym("cache");
+                    quote! {
+                        {
+                            let #f = #func;
+                            let #cache = std::cell::RefCell::new(
+                                std::collections::HashMap::<i32, i32>::new(),
+                            );
+                            move |x: i32| -> i32 {
+                                let cached = #cache.borrow().get(&x).copied();
+                                match cached {
+                                    Some(v) => v,
+                                    None => {
+                                        let v = #f(x);
+                                        #cache.borrow_mut().insert(x, v);
+                                        v
+                                    }
+                                }
+                            }
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("memoize requires exactly 1 argument") }
+                }
+            }
 
             // Error handling - try/catch equivalent
-            "try" => {
-                if args.len() >= 1 {
-                    let try_body = args[0].to_rust();
-                    if args.len() >= 2 {
-                        let catch_body = args[1].to_rust();
-                        quote! {
-                            {
-                                let result = std::panic::catch_unwind(|| {
-                                    #try_body
-                                });
-                                match result {
-                                    Ok(val) => val,
-                                    Err(_) => #catch_body,
-                                }
+            "try" | "try-verbose" => {
+                if args.is_empty() {
+                    let message = format!("{} requires at least a body", op_str);
+                    return quote! { compile_error!(#message) };
+                }
+                let try_body = args[0].to_rust();
+                let result = gensym("result");
+
+                // Clauses after the body: `(catch handler)` and `(finally cleanup)`
+                // in any order, or a bare fallback expression right after the body.
+                let mut fallback = None;
+                let mut cleanup = None;
+                for (i, clause) in args[1..].iter().enumerate() {
+                    if let Some(handler) = clause.clause_args("catch") {
+                        match handler {
+                            [handler] if fallback.is_none() => fallback = Some(handler.to_rust()),
+                            _ => {
+                                return quote! { compile_error!("try accepts one (catch handler) clause") }
+                            }
+                        }
+                    } else if let Some(body) = clause.clause_args("finally") {
+                        match body {
+                            [body] if cleanup.is_none() => cleanup = Some(body.to_rust()),
+                            _ => {
+                                return quote! { compile_error!("try accepts one (finally cleanup) clause") }
                             }
                         }
+                    } else if i == 0 {
+                        fallback = Some(clause.to_rust());
                     } else {
-                        quote! {
-                            {
-                                let result = std::panic::catch_unwind(|| {
-                                    #try_body
-                                });
-                                match result {
-                                    Ok(val) => val,
-                                    Err(_) => panic!("Unhandled error in try block"),
-                                }
+                        return quote! {
+                            compile_error!("try clauses must be (catch handler) or (finally cleanup)")
+                        };
+                    }
+                }
+
+                // With a fallback, plain `try` keeps the caught panic off
+                // stderr. Each call site installs, once, a hook that defers to
+                // the previous one unless its thread-local flag is set, and
+                // only sets that flag around `catch_unwind`. Panics elsewhere,
+                // including on other threads, are still reported. No hook is
+                // installed while the thread is already unwinding, where
+                // `set_hook` itself would panic.
+                let caught = if op_str == "try" && fallback.is_some() {
+                    let hook = gensym("HOOK");
+                    let quiet = gensym("QUIET");
+                    let was_quiet = gensym("was_quiet");
+                    quote! {
+                        static #hook: std::sync::Once = std::sync::Once::new();
+                        std::thread_local! {
+                            static #quiet: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+                        }
+                        if !std::thread::panicking() {
+                            #hook.call_once(|| {
+                                let previous = std::panic::take_hook();
+                                std::panic::set_hook(Box::new(move |info| {
+                                    if !#quiet.try_with(|quiet| quiet.get()).unwrap_or(false) {
+                                        previous(info)
+                                    }
+                                }));
+                            });
+                        }
+                        let #was_quiet = #quiet.with(|quiet| quiet.replace(true));
+                        let #result = std::panic::catch_unwind(|| {
+                            #try_body
+                        });
+                        #quiet.with(|quiet| quiet.set(#was_quiet));
+                    }
+                } else {
+                    quote! {
+                        let #result = std::panic::catch_unwind(|| {
+                            #try_body
+                        });
+                    }
+                };
+                let fallback =
+                    fallback.unwrap_or_else(|| quote! { panic!("Unhandled error in try block") });
+
+                // `finally` runs from a drop guard, so it also runs when the
+                // fallback itself panics.
+                let guard = cleanup.map(|cleanup| {
+                    let finally = gensym("Finally");
+                    let guard = gensym("guard");
+                    quote! {
+                        struct #finally<F: FnMut()>(F);
+                        impl<F: FnMut()> Drop for #finally<F> {
+                            fn drop(&mut self) {
+                                (self.0)()
+                            }
+                        }
+                        let #guard = #finally(|| {
+                            let _ = #cleanup;
+                        });
+                    }
+                });
+                quote! {
+                    {
+                        #guard
+                        #caught
+                        match #result {
+                            Ok(val) => val,
+                            Err(_) => #fallback,
+                        }
+                    }
+                }
+            }
+            "throw" => {
+                if args.len() == 1 {
+                    let message = args[0].to_rust();
+                    let throw = gensym("throw");
+                    // Generic over the return type rather than `!`, so that
+                    // `(try (throw "boom") 99)` infers `i32` for the try body
+                    // instead of falling back to `()`.
+                    quote! {
+                        {
+                            fn #throw<T>(message: impl core::fmt::Display) -> T {
+                                panic!("{}", message)
                             }
+                            #throw(#message)
                         }
                     }
                 } else {
-                    quote! { compile_error!("try requires at least a body") }
+                    quote! { compile_error!("throw requires exactly 1 argument") }
                 }
             }
             // Block/do
-            "do" => {
-                let statements = args.iter().map(|e| e.to_rust());
-                quote! { { #(#statements);* } }
+            "do" => match args {
+                [LispExpr::Keyword(kw), ty, forms @ ..] if kw == "as" => {
+                    let LispExpr::Symbol(ty) = ty else {
+                        return quote! { compile_error!("do :as requires a type name") };
+                    };
+                    let block = LispExpr::to_rust_block(forms);
+                    let value = gensym("value");
+                    quote! { { let #value: #ty = #block; #value } }
+                }
+                _ => LispExpr::to_rust_block(args),
+            },
+            "def" => {
+                quote! { compile_error!("def is only valid directly inside lisp! or a do block") }
             }
 
             // Variable capture - with-vars syntax
@@ -629,21 +2023,32 @@ impl LispExpr {
                 if args.len() >= 2 {
                     if let LispExpr::Vector(vars) = &args[0] {
                         let body = &args[1];
-                        let var_captures: Vec<_> = vars
-                            .iter()
-                            .filter_map(|v| {
-                                if let LispExpr::Symbol(name) = v {
-                                    Some(name)
-                                } else {
-                                    None
+                        let mut var_captures = Vec::new();
+                        let mut vars = vars.iter().peekable();
+                        while let Some(var) = vars.next() {
+                            let LispExpr::Symbol(outer) = var else {
+                                continue;
+                            };
+                            // `outer as inner` binds the captured variable under a new name
+                            if matches!(vars.peek(), Some(LispExpr::Symbol(kw)) if kw == "as") {
+                                vars.next();
+                                match vars.next() {
+                                    Some(LispExpr::Symbol(inner)) => {
+                                        var_captures.push(quote! { let #inner = #outer; })
+                                    }
+                                    _ => {
+                                        return quote! { compile_error!("with-vars: `as` must be followed by a name") };
+                                    }
                                 }
-                            })
-                            .collect();
+                            } else {
+                                var_captures.push(quote! { let #outer = #outer; });
+                            }
+                        }
 
                         let body_tokens = body.to_rust();
                         quote! {
                             {
-                                #(let #var_captures = #var_captures;)*
+                                #(#var_captures)*
                                 #body_tokens
                             }
                         }
@@ -656,118 +2061,1149 @@ impl LispExpr {
             }
 
             // While loop
+            // The body runs for its side effects only, so it may have any type
+            // and the loop itself always evaluates to `()`.
             "while" => {
                 if args.len() == 2 {
                     let condition = args[0].to_rust();
                     let body = args[1].to_rust();
+                    quote! {
+                        while (#condition) {
+                            let _ = #body;
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("While requires condition and body") }
+                }
+            }
+
+            // While loop collecting the value of each iteration
+            "while-collect" => {
+                if args.len() == 2 {
+                    let condition = args[0].to_rust();
+                    let body = args[1].to_rust();
+                    let results = gensym("results");
                     quote! {
                         {
-                            let mut result = ();
+                            let mut #results = Vec::new();
                             while (#condition) {
-                                result = #body;
+                                #results.push(#body);
+                            }
+                            #results
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("while-collect requires condition and body") }
+                }
+            }
+
+            // For-like loop (dotimes)
+            "dotimes" => {
+                if args.len() == 3 {
+                    if let LispExpr::Symbol(var) = &args[0] {
+                        let count = args[1].to_rust();
+                        let body = args[2].to_rust();
+                        quote! {
+                            {
+                                for #var in 0..(#count) {
+                                    let _ = #body;
+                                }
+                            }
+                        }
+                    } else {
+                        quote! { compile_error!("dotimes requires variable name") }
+                    }
+                } else {
+                    quote! { compile_error!("dotimes requires var, count, and body") }
+                }
+            }
+
+            // Accumulating loop (dotimes-sum)
+            "dotimes-sum" => {
+                if args.len() == 3 {
+                    if let LispExpr::Symbol(var) = &args[0] {
+                        let count = args[1].to_rust();
+                        let body = args[2].to_rust();
+                        quote! {
+                            (0..(#count)).fold(0, |acc, #var| acc + (#body))
+                        }
+                    } else {
+                        quote! { compile_error!("dotimes-sum requires variable name") }
+                    }
+                } else {
+                    quote! { compile_error!("dotimes-sum requires var, count, and body") }
+                }
+            }
+
+            // Element iteration (doseq)
+            "doseq" => {
+                if args.len() == 2 {
+                    match &args[0] {
+                        LispExpr::Vector(binding) if binding.len() == 2 => {
+                            if let LispExpr::Symbol(var) = &binding[0] {
+                                let coll = binding[1].to_rust();
+                                let body = args[1].to_rust();
+                                quote! {
+                                    {
+                                        for #var in (#coll) {
+                                            let _ = #body;
+                                        }
+                                    }
+                                }
+                            } else {
+                                quote! { compile_error!("doseq binding must start with a variable name") }
+                            }
+                        }
+                        _ => {
+                            quote! { compile_error!("doseq requires a binding vector of the form [var coll]") }
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("doseq requires a binding vector and body") }
+                }
+            }
+            "doseq-indexed" => {
+                if args.len() == 2 {
+                    match &args[0] {
+                        LispExpr::Vector(binding) => match binding.as_slice() {
+                            [LispExpr::Symbol(index), LispExpr::Symbol(var), coll] => {
+                                let coll = coll.to_rust();
+                                let body = args[1].to_rust();
+                                quote! {
+                                    {
+                                        for (#index, #var) in (#coll).iter().copied().enumerate() {
+                                            let #index = #index as i32;
+                                            let _ = #body;
+                                        }
+                                    }
+                                }
+                            }
+                            _ => quote! {
+                                compile_error!("doseq-indexed binding format: [index var coll]")
+                            },
+                        },
+                        _ => quote! { compile_error!("doseq-indexed requires a binding vector") },
+                    }
+                } else {
+                    quote! { compile_error!("doseq-indexed requires a binding vector and body") }
+                }
+            }
+
+            // Literal dispatch (case)
+            "case" => {
+                if args.len() >= 3 {
+                    let subject = args[0].to_rust();
+                    let clauses = &args[1..];
+                    let pairs = clauses.chunks_exact(2);
+                    let results = pairs.clone().map(|pair| &pair[1]);
+                    if let Some(error) =
+                        LispExpr::check_branch_literals("case", results.chain(pairs.remainder()))
+                    {
+                        return error;
+                    }
+                    let default = match pairs.remainder() {
+                        [default] => default.to_rust(),
+                        _ => quote! { panic!("case: no clause matched") },
+                    };
+                    // String keys match against the subject as a `&str`, so
+                    // `String` and `&str` subjects both work.
+                    let string_keys = pairs
+                        .clone()
+                        .any(|pair| matches!(pair[0], LispExpr::Literal(Lit::Str(_))));
+                    let subject = if string_keys {
+                        quote! { ::core::convert::AsRef::<str>::as_ref(&(#subject)) }
+                    } else {
+                        subject
+                    };
+                    let mut arms = Vec::new();
+                    for pair in pairs {
+                        match &pair[0] {
+                            key @ (LispExpr::Literal(_) | LispExpr::Char(_)) => {
+                                let key = key.to_rust();
+                                let result = pair[1].to_rust();
+                                arms.push(quote! { #key => #result, });
+                            }
+                            _ => {
+                                return quote! { compile_error!("case keys must be literals") };
+                            }
+                        }
+                    }
+                    quote! {
+                        match #subject {
+                            #(#arms)*
+                            _ => #default,
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("case requires a value and at least one key/result pair") }
+                }
+            }
+
+            "condp" => {
+                if args.len() >= 4 {
+                    let subject = gensym("subject");
+                    let value = args[1].to_rust();
+                    let clauses = args[2..].chunks_exact(2);
+                    let results = clauses.clone().map(|clause| &clause[1]);
+                    if let Some(error) =
+                        LispExpr::check_branch_literals("condp", results.chain(clauses.remainder()))
+                    {
+                        return error;
+                    }
+                    let mut result = match clauses.remainder() {
+                        [default] => default.to_rust(),
+                        _ => quote! { panic!("condp: no clause matched") },
+                    };
+                    let pred = gensym("pred");
+                    let bind_pred = match &args[0] {
+                        LispExpr::Operator(_) => None,
+                        func => {
+                            let func = func.to_rust();
+                            Some(quote! { let #pred = #func; })
+                        }
+                    };
+                    for clause in clauses.rev() {
+                        let test = match &args[0] {
+                            LispExpr::Operator(op) => self.expand_operation(
+                                op,
+                                &[LispExpr::Symbol(subject.clone()), clause[0].clone()],
+                            ),
+                            _ => {
+                                let test_value = clause[0].to_rust();
+                                quote! { #pred(#subject, #test_value) }
+                            }
+                        };
+                        let clause_result = clause[1].to_rust();
+                        result = quote! { if #test { #clause_result } else { #result } };
+                    }
+                    quote! {
+                        {
+                            let #subject = #value;
+                            #bind_pred
+                            #result
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("condp requires a predicate, a subject and at least one test/result pair") }
+                }
+            }
+
+            // Conditional threading (cond->)
+            "cond->" => {
+                if !args.is_empty() && args.len() % 2 == 1 {
+                    let init = args[0].to_rust();
+                    let value = gensym("v");
+                    let steps = args[1..].chunks(2).map(|clause| {
+                        let cond = clause[0].to_rust();
+                        let step = LispExpr::thread_first(&clause[1], &value).to_rust();
+                        quote! { let #value = if #cond { #step } else { #value }; }
+                    });
+                    quote! {
+                        {
+                            let #value = #init;
+                            #(#steps)*
+                            #value
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("cond-> requires a value followed by condition/step pairs") }
+                }
+            }
+
+            // Named threading (as->): each step rebinds the name, so shadowing
+            // substitutes the running value wherever the name appears.
+            "as->" => {
+                if args.len() >= 2 {
+                    if let LispExpr::Symbol(name) = &args[1] {
+                        let init = args[0].to_rust();
+                        let steps: Vec<_> = scoped(|| {
+                            bind_arity(name, None);
+                            args[2..].iter().map(|step| step.to_rust()).collect()
+                        });
+                        quote! {
+                            {
+                                let #name = #init;
+                                #(let #name = #steps;)*
+                                #name
                             }
-                            result
                         }
+                    } else {
+                        quote! { compile_error!("as-> requires a symbol to bind the value to") }
+                    }
+                } else {
+                    quote! { compile_error!("as-> requires a value and a binding name") }
+                }
+            }
+
+            // Nil-safe threading (some->): each step runs inside `and_then`.
+            "some->" => {
+                if !args.is_empty() {
+                    let init = args[0].to_rust();
+                    let value = gensym("v");
+                    let steps = args[1..]
+                        .iter()
+                        .map(|step| LispExpr::thread_first(step, &value).to_rust());
+                    quote! {
+                        (#init) #(.and_then(|#value| #steps))*
+                    }
+                } else {
+                    quote! { compile_error!("some-> requires an Option to thread") }
+                }
+            }
+
+            // Side-effecting threading (doto): every step sees the same value,
+            // and the step results are discarded.
+            "doto" => {
+                if !args.is_empty() {
+                    let init = args[0].to_rust();
+                    let value = gensym("v");
+                    let steps = args[1..]
+                        .iter()
+                        .map(|step| LispExpr::thread_first(step, &value).to_rust());
+                    quote! {
+                        {
+                            let #value = #init;
+                            #(#steps;)*
+                            #value
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("doto requires a value to operate on") }
+                }
+            }
+
+            // List comprehension (for)
+            "for" => {
+                if args.len() == 2 {
+                    let body = args[1].to_rust();
+                    match &args[0] {
+                        LispExpr::Vector(binding) => match binding.as_slice() {
+                            [LispExpr::Symbol(var), coll] => {
+                                let coll = coll.to_rust();
+                                quote! {
+                                    (#coll).iter().copied().map(|#var| #body).collect::<Vec<_>>()
+                                }
+                            }
+                            [LispExpr::Symbol(var), coll, LispExpr::Keyword(kw), pred]
+                                if kw == "when" =>
+                            {
+                                let coll = coll.to_rust();
+                                let pred = pred.to_rust();
+                                quote! {
+                                    (#coll)
+                                        .iter()
+                                        .copied()
+                                        .filter(|&#var| #pred)
+                                        .map(|#var| #body)
+                                        .collect::<Vec<_>>()
+                                }
+                            }
+                            _ => {
+                                quote! { compile_error!("for binding format: [var coll] or [var coll :when pred]") }
+                            }
+                        },
+                        _ => quote! { compile_error!("for requires a binding vector") },
+                    }
+                } else {
+                    quote! { compile_error!("for requires a binding vector and body") }
+                }
+            }
+
+            // Boolean operations
+            "and" => {
+                if args.is_empty() {
+                    quote! { true }
+                } else {
+                    infix_chain(args, quote! { && })
+                }
+            }
+            "or" => {
+                if args.is_empty() {
+                    quote! { false }
+                } else {
+                    infix_chain(args, quote! { || })
+                }
+            }
+            "xor" => {
+                if args.len() == 2 {
+                    let a = args[0].to_rust();
+                    let b = args[1].to_rust();
+                    quote! { (#a) ^ (#b) }
+                } else {
+                    quote! { compile_error!("xor requires exactly 2 arguments") }
+                }
+            }
+            "nand" | "nor" => {
+                let folded =
+                    self.expand_operation(if op_str == "nand" { "and" } else { "or" }, args);
+                quote! { !(#folded) }
+            }
+            "or-val" | "and-val" => {
+                if args.len() == 1 {
+                    args[0].to_rust()
+                } else if !args.is_empty() {
+                    // `or-val` stops at the first truthy operand, `and-val` at the first falsy one.
+                    let truthy = gensym("truthy");
+                    let stop = if op_str == "or-val" {
+                        quote! { #truthy(&v) }
+                    } else {
+                        quote! { !#truthy(&v) }
+                    };
+                    let mut terms = args.iter().rev().map(|e| e.to_rust());
+                    let mut result = terms.next().unwrap();
+                    for term in terms {
+                        result = quote! {
+                            match #term {
+                                v if #stop => v,
+                                _ => #result,
+                            }
+                        };
+                    }
+                    quote! {
+                        {
+                            fn #truthy<T: Default + PartialEq>(v: &T) -> bool {
+                                *v != T::default()
+                            }
+                            #result
+                        }
+                    }
+                } else {
+                    let message = format!("{} requires at least 1 argument", op_str);
+                    quote! { compile_error!(#message) }
+                }
+            }
+            "not" => {
+                if args.len() == 1 {
+                    let arg = args[0].to_rust();
+                    quote! { !(#arg) }
+                } else {
+                    quote! { compile_error!("Not requires exactly 1 argument") }
+                }
+            }
+
+            // List/Vector operations
+            "first" => {
+                if args.len() == 1 {
+                    let arg = args[0].to_rust();
+                    quote! { (#arg).first().copied().unwrap_or_default() }
+                } else {
+                    quote! { compile_error!("First requires exactly 1 argument") }
+                }
+            }
+            "rest" => {
+                if args.len() == 1 {
+                    let arg = args[0].to_rust();
+                    quote! { (#arg).get(1..).map(|s| s.to_vec()).unwrap_or_default() }
+                } else {
+                    quote! { compile_error!("Rest requires exactly 1 argument") }
+                }
+            }
+            "last" => {
+                if args.len() == 1 {
+                    let arg = args[0].to_rust();
+                    quote! { (#arg).last().copied().unwrap_or_default() }
+                } else {
+                    quote! { compile_error!("last requires exactly 1 argument") }
+                }
+            }
+            "second" | "third" => {
+                if args.len() == 1 {
+                    let arg = args[0].to_rust();
+                    let index: usize = if op_str == "second" { 1 } else { 2 };
+                    quote! { (#arg).get(#index).copied().unwrap_or_default() }
+                } else {
+                    let message = format!("{} requires exactly 1 argument", op_str);
+                    quote! { compile_error!(#message) }
+                }
+            }
+            "nthrest" => {
+                if args.len() == 2 {
+                    let n = args[0].to_rust();
+                    let coll = args[1].to_rust();
+                    // Negative counts clamp to 0 before the cast
+                    quote! {
+                        (#coll)
+                            .get(core::cmp::max(#n, 0) as usize..)
+                            .map(|s| s.to_vec())
+                            .unwrap_or_default()
+                    }
+                } else {
+                    quote! { compile_error!("nthrest requires a count and a collection") }
+                }
+            }
+            "take-nth" => match args {
+                [LispExpr::Literal(Lit::Int(n)), _] if n.base10_digits() == "0" => {
+                    quote_spanned! { n.span()=> compile_error!("take-nth step must be positive") }
+                }
+                [n, coll] => {
+                    let n = n.to_rust();
+                    let coll = coll.to_rust();
+                    let step = gensym("step");
+                    quote! {
+                        {
+                            let #step = #n;
+                            assert!(#step > 0, "take-nth step must be positive, got {}", #step);
+                            (#coll).iter().copied().step_by(#step as usize).collect::<Vec<_>>()
+                        }
+                    }
+                }
+                _ => quote! { compile_error!("take-nth requires a step and a collection") },
+            },
+            "split-at" => {
+                if args.len() == 2 {
+                    let n = args[0].to_rust();
+                    let coll = args[1].to_rust();
+                    let (v, head, tail) = (gensym("v"), gensym("head"), gensym("tail"));
+                    quote! {
+                        {
+                            let #v = #coll;
+                            // Negative counts clamp to 0 before the cast
+                            let (#head, #tail) =
+                                #v.split_at((core::cmp::max(#n, 0) as usize).min(#v.len()));
+                            (#head.to_vec(), #tail.to_vec())
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("split-at requires a count and a collection") }
+                }
+            }
+            "slice" => {
+                if args.len() == 3 {
+                    let coll = args[0].to_rust();
+                    let start = args[1].to_rust();
+                    let end = args[2].to_rust();
+                    let (v, lo, hi) = (gensym("v"), gensym("lo"), gensym("hi"));
+                    quote! {
+                        {
+                            let #v = #coll;
+                            // Negative bounds clamp to 0 before the cast
+                            let #hi = (core::cmp::max(#end, 0) as usize).min(#v.len());
+                            let #lo = (core::cmp::max(#start, 0) as usize).min(#hi);
+                            #v[#lo..#hi].to_vec()
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("slice requires a collection, a start and an end") }
+                }
+            }
+            "butlast" => {
+                if args.len() == 1 {
+                    let arg = args[0].to_rust();
+                    let v = gensym("v");
+                    quote! { { let #v = #arg; #v[..#v.len().saturating_sub(1)].to_vec() } }
+                } else {
+                    quote! { compile_error!("butlast requires exactly 1 argument") }
+                }
+            }
+            "list" => {
+                let elements = args.iter().map(|e| e.to_rust());
+                quote! { vec![#(#elements),*] }
+            }
+            "vec-of" => match args {
+                [LispExpr::Symbol(ty)] => quote! { Vec::<#ty>::new() },
+                [LispExpr::Symbol(ty), elements @ ..] => {
+                    let elements = elements.iter().map(|e| e.to_rust());
+                    let v = gensym("v");
+                    quote! { { let #v: Vec<#ty> = vec![#(#elements),*]; #v } }
+                }
+                _ => {
+                    quote! { compile_error!("vec-of requires an element type followed by elements") }
+                }
+            },
+            "cons" => {
+                if args.len() == 2 {
+                    let elem = args[0].to_rust();
+                    let list = args[1].to_rust();
+                    let result = gensym("result");
+                    quote! { { let mut #result = vec![(#elem)]; #result.extend(#list); #result } }
+                } else {
+                    quote! { compile_error!("Cons requires exactly 2 arguments") }
+                }
+            }
+            "char-at" => {
+                if args.len() == 2 {
+                    let string = args[0].to_rust();
+                    let index = args[1].to_rust();
+                    quote! {
+                        (#string)
+                            .chars()
+                            .nth((#index) as usize)
+                            .expect("char-at: index out of range")
+                    }
+                } else {
+                    quote! { compile_error!("char-at requires a string and an index") }
+                }
+            }
+            "ascii-code" => {
+                if args.len() == 1 {
+                    let c = args[0].to_rust();
+                    quote! { ((#c) as i32) }
+                } else {
+                    quote! { compile_error!("ascii-code requires exactly 1 argument") }
+                }
+            }
+            "code->char" => {
+                if args.len() == 1 {
+                    let n = args[0].to_rust();
+                    quote! { (((#n) as u8) as char) }
+                } else {
+                    quote! { compile_error!("code->char requires exactly 1 argument") }
+                }
+            }
+            "contains?" => {
+                if args.len() == 2 {
+                    let coll = args[0].to_rust();
+                    let value = args[1].to_rust();
+                    quote! { (#coll).contains(&(#value)) }
+                } else {
+                    quote! { compile_error!("contains? requires exactly 2 arguments") }
+                }
+            }
+            "has-key?" => {
+                if args.len() == 2 {
+                    let map = args[0].to_rust();
+                    let key = args[1].to_rust();
+                    quote! { (#map).contains_key(&(#key)) }
+                } else {
+                    quote! { compile_error!("has-key? requires exactly 2 arguments") }
+                }
+            }
+            "select-keys" => {
+                if args.len() == 2 {
+                    let map = args[0].to_rust();
+                    let keys = args[1].to_rust();
+                    let source = gensym("source");
+                    let selected = gensym("selected");
+                    quote! {
+                        {
+                            let #source = &(#map);
+                            let mut #selected = std::collections::HashMap::new();
+                            for key in #keys {
+                                if let Some(value) = #source.get(&key) {
+                                    #selected.insert(key, value.clone());
+                                }
+                            }
+                            #selected
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("select-keys requires a map and a vector of keys") }
+                }
+            }
+            "sorted-pairs" => {
+                if args.len() == 1 {
+                    let map = args[0].to_rust();
+                    let pairs = gensym("pairs");
+                    quote! {
+                        {
+                            let mut #pairs: Vec<_> = (#map)
+                                .iter()
+                                .map(|(k, v)| (k.clone(), v.clone()))
+                                .collect();
+                            #pairs.sort_by(|(a, _), (b, _)| a.cmp(b));
+                            #pairs
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("sorted-pairs requires exactly 1 argument") }
+                }
+            }
+            "merge" => {
+                if !args.is_empty() {
+                    let first = args[0].to_rust();
+                    let rest = args[1..].iter().map(|e| e.to_rust());
+                    let merged = gensym("merged");
+                    quote! {
+                        {
+                            let mut #merged = (#first).clone();
+                            #(#merged.extend((#rest).clone());)*
+                            #merged
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("merge requires at least one map") }
+                }
+            }
+            "count" => {
+                if args.len() == 1 {
+                    let arg = args[0].to_rust();
+                    quote! { (#arg).len() }
+                } else {
+                    quote! { compile_error!("Count requires exactly 1 argument") }
+                }
+            }
+
+            "repeat" => {
+                if args.len() == 2 {
+                    let n = args[0].to_rust();
+                    let value = args[1].to_rust();
+                    // Negative counts clamp to 0 before the cast
+                    quote! { vec![#value; core::cmp::max(#n, 0) as usize] }
+                } else {
+                    quote! { compile_error!("repeat requires exactly 2 arguments") }
+                }
+            }
+            "assoc-in" => {
+                if args.len() == 3 {
+                    let path = match &args[1] {
+                        LispExpr::Vector(path) if !path.is_empty() => path
+                            .iter()
+                            .map(|index| match index {
+                                LispExpr::Literal(Lit::Int(index)) => Some(index),
+                                _ => None,
+                            })
+                            .collect::<Option<Vec<_>>>(),
+                        _ => None,
+                    };
+                    match path {
+                        Some(path) => {
+                            let coll = args[0].to_rust();
+                            let value = args[2].to_rust();
+                            let updated = gensym("updated");
+                            quote! {
+                                {
+                                    let mut #updated = (#coll).clone();
+                                    #updated #([#path])* = #value;
+                                    #updated
+                                }
+                            }
+                        }
+                        None => quote! {
+                            compile_error!("assoc-in requires a vector of integer literals as its path")
+                        },
+                    }
+                } else {
+                    quote! { compile_error!("assoc-in requires exactly 3 arguments") }
+                }
+            }
+            "assoc-vec" => {
+                if args.len() == 3 {
+                    let coll = args[0].to_rust();
+                    let index = args[1].to_rust();
+                    let value = args[2].to_rust();
+                    let updated = gensym("updated");
+                    let i = gensym("i");
+                    let slot = gensym("slot");
+                    quote! {
+                        {
+                            let mut #updated = (#coll).clone();
+                            let #i = (#index) as usize;
+                            match #updated.get_mut(#i) {
+                                Some(#slot) => *#slot = #value,
+                                None => panic!(
+                                    "assoc-vec: index {} out of bounds for length {}",
+                                    #i,
+                                    #updated.len()
+                                ),
+                            }
+                            #updated
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("assoc-vec requires a vector, an index and a value") }
+                }
+            }
+            "update" => {
+                if args.len() == 3 {
+                    let coll = args[0].to_rust();
+                    let index = args[1].to_rust();
+                    let func = args[2].to_rust();
+                    let updated = gensym("updated");
+                    let i = gensym("i");
+                    let f = gensym("f");
+                    quote! {
+                        {
+                            let mut #updated = (#coll).clone();
+                            let #i = (#index) as usize;
+                            let #f = #func;
+                            #updated[#i] = #f(#updated[#i]);
+                            #updated
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("update requires a collection, an index and a function") }
+                }
+            }
+            "vec->array" => match args {
+                [coll, LispExpr::Literal(Lit::Int(len))] => {
+                    let coll = coll.to_rust();
+                    let items = gensym("items");
+                    quote! {
+                        {
+                            let #items: Vec<_> = (#coll).to_vec();
+                            <[_; #len]>::try_from(#items).unwrap_or_else(|items: Vec<_>| {
+                                panic!(
+                                    "vec->array: expected {} elements, found {}",
+                                    #len,
+                                    items.len()
+                                )
+                            })
+                        }
+                    }
+                }
+                _ => {
+                    quote! { compile_error!("vec->array requires a vector and an integer literal length") }
+                }
+            },
+            "make-grid" => {
+                if args.len() == 3 {
+                    let rows = args[0].to_rust();
+                    let cols = args[1].to_rust();
+                    let init = args[2].to_rust();
+                    // Negative dimensions clamp to 0 before the cast
+                    quote! {
+                        vec![
+                            vec![#init; core::cmp::max(#cols, 0) as usize];
+                            core::cmp::max(#rows, 0) as usize
+                        ]
+                    }
+                } else {
+                    quote! { compile_error!("make-grid requires exactly 3 arguments") }
+                }
+            }
+            "grid-ref" => {
+                if args.len() == 3 {
+                    let grid = args[0].to_rust();
+                    let row = args[1].to_rust();
+                    let col = args[2].to_rust();
+                    let (r, c) = (gensym("r"), gensym("c"));
+                    // A negative index would wrap to a huge `usize` and report
+                    // a confusing out-of-bounds error, so name it instead
+                    quote! {
+                        {
+                            let #r = #row;
+                            let #c = #col;
+                            let #r = usize::try_from(#r).unwrap_or_else(|_| {
+                                panic!("grid-ref: row index {} is negative", #r)
+                            });
+                            let #c = usize::try_from(#c).unwrap_or_else(|_| {
+                                panic!("grid-ref: column index {} is negative", #c)
+                            });
+                            (#grid)[#r][#c]
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("grid-ref requires exactly 3 arguments") }
+                }
+            }
+            "not-empty" => {
+                if args.len() == 1 {
+                    let arg = args[0].to_rust();
+                    let v = gensym("v");
+                    quote! { { let #v = #arg; if #v.is_empty() { None } else { Some(#v) } } }
+                } else {
+                    quote! { compile_error!("not-empty requires exactly 1 argument") }
+                }
+            }
+            "chunk-by" => {
+                if args.len() == 2 {
+                    let func = args[0].to_rust();
+                    let coll = args[1].to_rust();
+                    let f = gensym("f");
+                    let chunks = gensym("chunks");
+                    let last_key = gensym("last_key");
+                    quote! {
+                        {
+                            let #f = #func;
+                            let mut #chunks: Vec<Vec<_>> = Vec::new();
+                            let mut #last_key = None;
+                            for x in (#coll).iter().copied() {
+                                let key = #f(x);
+                                match #chunks.last_mut() {
+                                    Some(chunk) if #last_key.as_ref() == Some(&key) => chunk.push(x),
+                                    _ => #chunks.push(vec![x]),
+                                }
+                                #last_key = Some(key);
+                            }
+                            #chunks
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("chunk-by requires exactly 2 arguments") }
+                }
+            }
+            "group-by" => {
+                if args.len() == 2 {
+                    let func = args[0].to_rust();
+                    let coll = args[1].to_rust();
+                    let f = gensym("f");
+                    let groups = gensym("groups");
+                    quote! {
+                        {
+                            let #f = #func;
+                            let mut #groups: std::collections::HashMap<_, Vec<_>> =
+                                std::collections::HashMap::new();
+                            for x in (#coll).iter().copied() {
+                                #groups.entry(#f(x)).or_default().push(x);
+                            }
+                            #groups
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("group-by requires exactly 2 arguments") }
+                }
+            }
+
+            "reductions" => {
+                if args.len() == 3 {
+                    let func = args[0].to_rust();
+                    let init = args[1].to_rust();
+                    let coll = args[2].to_rust();
+                    let f = gensym("f");
+                    let acc = gensym("acc");
+                    let results = gensym("results");
+                    quote! {
+                        {
+                            let #f = #func;
+                            let mut #acc = #init;
+                            let mut #results = vec![#acc];
+                            for x in (#coll).iter().copied() {
+                                #acc = #f(#acc, x);
+                                #results.push(#acc);
+                            }
+                            #results
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("reductions requires a function, an initial value and a collection") }
+                }
+            }
+            "reduce-while" => {
+                if args.len() == 4 {
+                    let pred = args[0].to_rust();
+                    let func = args[1].to_rust();
+                    let init = args[2].to_rust();
+                    let coll = args[3].to_rust();
+                    let (p, f, acc, x) = (gensym("pred"), gensym("f"), gensym("acc"), gensym("x"));
+                    quote! {
+                        {
+                            let #p = #pred;
+                            let #f = #func;
+                            let mut #acc = #init;
+                            for #x in (#coll).iter().copied() {
+                                if !#p(#acc) {
+                                    break;
+                                }
+                                #acc = #f(#acc, #x);
+                            }
+                            #acc
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("reduce-while requires a predicate, a function, an initial value and a collection") }
+                }
+            }
+            "iterate" => {
+                if args.len() == 3 {
+                    let func = args[0].to_rust();
+                    let init = args[1].to_rust();
+                    let n = args[2].to_rust();
+                    let f = gensym("f");
+                    let n_var = gensym("n");
+                    let results = gensym("results");
+                    quote! {
+                        {
+                            let #f = #func;
+                            // A negative count yields no values
+                            let #n_var = core::cmp::max(#n, 0) as usize;
+                            let mut #results = Vec::with_capacity(#n_var);
+                            if #n_var > 0 {
+                                #results.push(#init);
+                            }
+                            // Stop applying `f` once the last needed value exists
+                            while #results.len() < #n_var {
+                                let next = #f(#results[#results.len() - 1]);
+                                #results.push(next);
+                            }
+                            #results
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("iterate requires a function, an initial value and a count") }
+                }
+            }
+            "mapcat" => {
+                if args.len() == 2 {
+                    let func = args[0].to_rust();
+                    let coll = args[1].to_rust();
+                    let f = gensym("f");
+                    quote! {
+                        {
+                            let #f = #func;
+                            (#coll).iter().copied().flat_map(#f).collect::<Vec<_>>()
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("mapcat requires a function and a collection") }
+                }
+            }
+            "keep" => {
+                if args.len() == 2 {
+                    let func = args[0].to_rust();
+                    let coll = args[1].to_rust();
+                    let f = gensym("f");
+                    quote! {
+                        {
+                            let #f = #func;
+                            (#coll).iter().copied().filter_map(#f).collect::<Vec<_>>()
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("keep requires a function and a collection") }
+                }
+            }
+            "repeatedly" => {
+                if args.len() == 2 {
+                    let n = args[0].to_rust();
+                    let func = args[1].to_rust();
+                    // `repeat_with` takes the function by value, so stateful
+                    // `FnMut` thunks work without a `mut` binding.
+                    // Negative counts clamp to 0 before the cast
+                    quote! {
+                        core::iter::repeat_with(#func)
+                            .take(core::cmp::max(#n, 0) as usize)
+                            .collect::<Vec<_>>()
                     }
                 } else {
-                    quote! { compile_error!("While requires condition and body") }
+                    quote! { compile_error!("repeatedly requires a count and a function") }
                 }
             }
-
-            // For-like loop (dotimes)
-            "dotimes" => {
-                if args.len() == 3 {
-                    if let LispExpr::Symbol(var) = &args[0] {
-                        let count = args[1].to_rust();
-                        let body = args[2].to_rust();
-                        quote! {
-                            {
-                                for #var in 0..(#count) {
-                                    let _ = #body;
-                                }
-                                ()
-                            }
-                        }
+            "max-by" | "min-by" => {
+                if args.len() == 2 {
+                    let key = args[0].to_rust();
+                    let coll = args[1].to_rust();
+                    let method = if op_str == "max-by" {
+                        quote! { max_by_key }
                     } else {
-                        quote! { compile_error!("dotimes requires variable name") }
+                        quote! { min_by_key }
+                    };
+                    let f = gensym("f");
+                    quote! {
+                        {
+                            let #f = #key;
+                            (#coll).iter().copied().#method(|&x| #f(x))
+                        }
                     }
                 } else {
-                    quote! { compile_error!("dotimes requires var, count, and body") }
+                    let message = format!("{} requires exactly 2 arguments", op_str);
+                    quote! { compile_error!(#message) }
                 }
             }
-
-            // Boolean operations
-            "and" => {
-                if args.len() >= 2 {
-                    let terms = args.iter().map(|e| e.to_rust());
-                    let mut result = quote! { true };
-                    for term in terms {
-                        result = quote! { (#result) && (#term) };
+            "min-max" => {
+                if args.len() == 1 {
+                    let coll = args[0].to_rust();
+                    let items = gensym("items");
+                    let iter = gensym("iter");
+                    let first = gensym("first");
+                    quote! {
+                        {
+                            let #items = #coll;
+                            let mut #iter = #items.iter().copied();
+                            let #first = #iter.next().expect("min-max: empty collection");
+                            #iter.fold((#first, #first), |(lo, hi), x| {
+                                (if x < lo { x } else { lo }, if x > hi { x } else { hi })
+                            })
+                        }
                     }
-                    result
                 } else {
-                    quote! { compile_error!("And requires at least 2 arguments") }
+                    quote! { compile_error!("min-max requires exactly 1 argument") }
                 }
             }
-            "or" => {
-                if args.len() >= 2 {
-                    let terms = args.iter().map(|e| e.to_rust());
-                    let mut result = quote! { false };
-                    for term in terms {
-                        result = quote! { (#result) || (#term) };
+            "find-index" => {
+                if args.len() == 2 {
+                    let pred = args[0].to_rust();
+                    let coll = args[1].to_rust();
+                    let f = gensym("f");
+                    quote! {
+                        {
+                            let #f = #pred;
+                            (#coll).iter().position(|x| #f(*x)).map(|i| i as i32)
+                        }
                     }
-                    result
                 } else {
-                    quote! { compile_error!("Or requires at least 2 arguments") }
+                    quote! { compile_error!("find-index requires a predicate and a collection") }
                 }
             }
-            "not" => {
-                if args.len() == 1 {
-                    let arg = args[0].to_rust();
-                    quote! { !(#arg) }
+            "most-common" => {
+                if args.len() == 2 {
+                    let n = args[0].to_rust();
+                    let coll = args[1].to_rust();
+                    let counts = gensym("counts");
+                    quote! {
+                        {
+                            let mut #counts: Vec<(_, usize)> = Vec::new();
+                            for x in (#coll).iter().copied() {
+                                match #counts.iter_mut().find(|(k, _)| *k == x) {
+                                    Some(entry) => entry.1 += 1,
+                                    None => #counts.push((x, 1)),
+                                }
+                            }
+                            // Stable sort, so equally frequent elements stay in
+                            // the order they first appeared.
+                            #counts.sort_by(|a, b| b.1.cmp(&a.1));
+                            #counts.truncate((#n) as usize);
+                            #counts
+                        }
+                    }
                 } else {
-                    quote! { compile_error!("Not requires exactly 1 argument") }
+                    quote! { compile_error!("most-common requires exactly 2 arguments") }
                 }
             }
 
-            // List/Vector operations
-            "first" => {
+            // Mutable state
+            "atom" => {
                 if args.len() == 1 {
-                    let arg = args[0].to_rust();
-                    quote! { (#arg).first().copied().unwrap_or_default() }
+                    let init = args[0].to_rust();
+                    quote! { core::cell::RefCell::new(#init) }
                 } else {
-                    quote! { compile_error!("First requires exactly 1 argument") }
+                    quote! { compile_error!("atom requires exactly 1 argument") }
                 }
             }
-            "rest" => {
+            "deref" => {
                 if args.len() == 1 {
-                    let arg = args[0].to_rust();
-                    quote! { { let v = #arg; if v.len() > 1 { v[1..].to_vec() } else { vec![] } } }
+                    let atom = args[0].to_rust();
+                    let value = gensym("value");
+                    // Copy out in a statement so the `Ref` guard is dropped
+                    // before the atom when `deref` ends a block.
+                    quote! { { let #value = *(#atom).borrow(); #value } }
                 } else {
-                    quote! { compile_error!("Rest requires exactly 1 argument") }
+                    quote! { compile_error!("deref requires exactly 1 argument") }
                 }
             }
-            "cons" => {
+            "swap!" => {
                 if args.len() == 2 {
-                    let elem = args[0].to_rust();
-                    let list = args[1].to_rust();
-                    quote! { { let mut result = vec![(#elem)]; result.extend(#list); result } }
+                    let atom = args[0].to_rust();
+                    let func = args[1].to_rust();
+                    let cell = gensym("cell");
+                    let v = gensym("v");
+                    quote! {
+                        {
+                            let #cell = &(#atom);
+                            let #v = (#func)(*#cell.borrow());
+                            *#cell.borrow_mut() = #v;
+                        }
+                    }
                 } else {
-                    quote! { compile_error!("Cons requires exactly 2 arguments") }
+                    quote! { compile_error!("swap! requires an atom and a function") }
                 }
             }
-            "count" => {
-                if args.len() == 1 {
-                    let arg = args[0].to_rust();
-                    quote! { (#arg).len() }
-                } else {
-                    quote! { compile_error!("Count requires exactly 1 argument") }
+            "inc!" | "dec!" => match args {
+                [LispExpr::Symbol(var)] => {
+                    if op_str == "inc!" {
+                        quote! { #var += 1 }
+                    } else {
+                        quote! { #var -= 1 }
+                    }
                 }
-            }
+                _ => {
+                    let message = format!("{} requires a single mutable variable", op_str);
+                    quote! { compile_error!(#message) }
+                }
+            },
 
             // String operations
             "str" => {
-                if args.len() >= 1 {
+                if !args.is_empty() {
                     let string_parts = args.iter().map(|e| {
                         let arg = e.to_rust();
                         quote! { (#arg).to_string() }
@@ -777,44 +3213,178 @@ impl LispExpr {
                     quote! { String::new() }
                 }
             }
+            "str-repeat" => {
+                if args.len() == 2 {
+                    let string = args[0].to_rust();
+                    let n = args[1].to_rust();
+                    // Negative counts clamp to 0 before the cast
+                    quote! { (#string).repeat(core::cmp::max(#n, 0) as usize) }
+                } else {
+                    quote! { compile_error!("str-repeat requires a string and a count") }
+                }
+            }
+            "hex" | "bin" | "oct" => {
+                if args.len() == 1 {
+                    let n = args[0].to_rust();
+                    let spec = match op_str {
+                        "hex" => "{:#x}",
+                        "bin" => "{:#b}",
+                        _ => "{:#o}",
+                    };
+                    quote! { format!(#spec, #n) }
+                } else {
+                    let message = format!("{} requires exactly 1 argument", op_str);
+                    quote! { compile_error!(#message) }
+                }
+            }
+            "pad-left" | "pad-right" => {
+                if args.len() == 2 {
+                    let value = args[0].to_rust();
+                    let width = args[1].to_rust();
+                    let spec = if op_str == "pad-left" {
+                        "{:>1$}"
+                    } else {
+                        "{:<1$}"
+                    };
+                    // A negative width pads nothing rather than wrapping to
+                    // a huge `usize`
+                    quote! { format!(#spec, #value, core::cmp::max(#width, 0) as usize) }
+                } else {
+                    let message = format!("{} requires a value and a width", op_str);
+                    quote! { compile_error!(#message) }
+                }
+            }
 
             // Math utility functions
             "min" => {
                 if args.len() >= 2 {
-                    let first = args[0].to_rust();
-                    let rest = args[1..].iter().map(|e| e.to_rust());
-                    let mut result = quote! { (#first) };
-                    for term in rest {
-                        result = quote! { std::cmp::min(#result, #term) };
-                    }
-                    result
+                    call_chain(args, quote! { core::cmp::min })
                 } else {
                     quote! { compile_error!("min requires at least 2 arguments") }
                 }
             }
             "max" => {
                 if args.len() >= 2 {
-                    let first = args[0].to_rust();
-                    let rest = args[1..].iter().map(|e| e.to_rust());
-                    let mut result = quote! { (#first) };
-                    for term in rest {
-                        result = quote! { std::cmp::max(#result, #term) };
-                    }
-                    result
+                    call_chain(args, quote! { core::cmp::max })
                 } else {
                     quote! { compile_error!("max requires at least 2 arguments") }
                 }
             }
+            "gcd" | "lcm" => {
+                if args.len() >= 2 {
+                    let gcd = gensym("gcd");
+                    let lcm = gensym("lcm");
+                    let func = if op_str == "gcd" { &gcd } else { &lcm };
+                    let result = call_chain(args, quote! { #func });
+                    // Generic helpers so the integer type is inferred from the
+                    // arguments, like the other arithmetic operations. Negative
+                    // results are flipped with `zero - a` rather than `Neg`, so
+                    // unsigned types work too.
+                    let gcd_fn = quote! {
+                        fn #gcd<T>(a: T, b: T) -> T
+                        where
+                            T: Copy + PartialOrd + Default + core::ops::Rem<Output = T> + core::ops::Sub<Output = T>,
+                        {
+                            let zero = T::default();
+                            let (mut a, mut b) = (a, b);
+                            while b != zero {
+                                let t = a % b;
+                                a = b;
+                                b = t;
+                            }
+                            if a < zero { zero - a } else { a }
+                        }
+                    };
+                    let lcm_fn = if op_str == "lcm" {
+                        quote! {
+                            fn #lcm<T>(a: T, b: T) -> T
+                            where
+                                T: Copy
+                                    + PartialOrd
+                                    + Default
+                                    + core::ops::Rem<Output = T>
+                                    + core::ops::Sub<Output = T>
+                                    + core::ops::Div<Output = T>
+                                    + core::ops::Mul<Output = T>,
+                            {
+                                let zero = T::default();
+                                if a == zero || b == zero {
+                                    return zero;
+                                }
+                                let m = a / #gcd(a, b) * b;
+                                if m < zero { zero - m } else { m }
+                            }
+                        }
+                    } else {
+                        TokenStream::new()
+                    };
+                    quote! {
+                        {
+                            #gcd_fn
+                            #lcm_fn
+                            #result
+                        }
+                    }
+                } else {
+                    let message = format!("{} requires at least 2 arguments", op_str);
+                    quote! { compile_error!(#message) }
+                }
+            }
             "abs" => {
                 if args.len() == 1 {
                     let arg = args[0].to_rust();
-                    quote! { ((#arg) as i32).abs() }
+                    // A generic helper rather than `.abs()`, so unsuffixed
+                    // literals still infer their type (`i32` by default) while
+                    // floats and wider integers keep theirs.
+                    let abs = gensym("abs");
+                    quote! {
+                        {
+                            fn #abs<T>(x: T) -> T
+                            where
+                                T: PartialOrd + Default + core::ops::Neg<Output = T>,
+                            {
+                                if x < T::default() { -x } else { x }
+                            }
+                            #abs(#arg)
+                        }
+                    }
                 } else {
                     quote! { compile_error!("abs requires exactly 1 argument") }
                 }
             }
 
             // Additional utility functions
+            "bool->int" => {
+                if args.len() == 1 {
+                    let arg = args[0].to_rust();
+                    quote! { ((#arg) as i32) }
+                } else {
+                    quote! { compile_error!("bool->int requires exactly 1 argument") }
+                }
+            }
+            "count-true" => {
+                if args.len() == 1 {
+                    let coll = args[0].to_rust();
+                    quote! { (#coll).iter().copied().filter(|&b| b).count() }
+                } else {
+                    quote! { compile_error!("count-true requires exactly 1 argument") }
+                }
+            }
+            "count-if" => {
+                if args.len() == 2 {
+                    let pred = args[0].to_rust();
+                    let coll = args[1].to_rust();
+                    let f = gensym("f");
+                    quote! {
+                        {
+                            let #f = #pred;
+                            (#coll).iter().copied().filter(|x| #f(*x)).count() as i32
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("count-if requires a predicate and a collection") }
+                }
+            }
             "inc" => {
                 if args.len() == 1 {
                     let arg = args[0].to_rust();
@@ -871,23 +3441,410 @@ impl LispExpr {
                     quote! { compile_error!("odd requires exactly 1 argument") }
                 }
             }
+            "between?" | "in-range?" => {
+                if args.len() == 3 {
+                    let x = args[0].to_rust();
+                    let lo = args[1].to_rust();
+                    let hi = args[2].to_rust();
+                    if op_str == "between?" {
+                        quote! { ((#lo)..=(#hi)).contains(&(#x)) }
+                    } else {
+                        quote! { ((#lo)..(#hi)).contains(&(#x)) }
+                    }
+                } else {
+                    let message = format!("{} requires a value and two bounds", op_str);
+                    quote! { compile_error!(#message) }
+                }
+            }
+
+            "truthy?" | "falsy?" => {
+                if args.len() == 1 {
+                    let x = args[0].to_rust();
+                    if op_str == "truthy?" {
+                        quote! { (#x) != 0 }
+                    } else {
+                        quote! { (#x) == 0 }
+                    }
+                } else {
+                    let message = format!("{} requires exactly 1 argument", op_str);
+                    quote! { compile_error!(#message) }
+                }
+            }
+
+            // Environment
+            "env" => match args {
+                [name] => {
+                    let name = name.to_rust();
+                    quote! { std::env::var(#name).ok() }
+                }
+                [name, default] => {
+                    let name = name.to_rust();
+                    let default = default.to_rust();
+                    quote! { std::env::var(#name).unwrap_or_else(|_| (#default).to_string()) }
+                }
+                _ => {
+                    quote! { compile_error!("env requires a variable name and an optional default") }
+                }
+            },
+
+            "read-line" => {
+                if args.is_empty() {
+                    quote! {
+                        {
+                            let mut line = String::new();
+                            std::io::stdin()
+                                .read_line(&mut line)
+                                .expect("read-line: failed to read from stdin");
+                            line.trim().to_string()
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("read-line takes no arguments") }
+                }
+            }
+
+            "slurp" | "try-slurp" => {
+                if args.len() == 1 {
+                    let path = args[0].to_rust();
+                    let (path_ref, p) = (gensym("path_ref"), gensym("path"));
+                    let read = if op_str == "slurp" {
+                        quote! {
+                            std::fs::read_to_string(#p)
+                                .unwrap_or_else(|e| panic!("slurp: cannot read {}: {}", #p.display(), e))
+                        }
+                    } else {
+                        quote! { std::fs::read_to_string(#p) }
+                    };
+                    // Borrow the path so a `PathBuf` variable can be reused.
+                    quote! {
+                        {
+                            let #path_ref = &(#path);
+                            let #p: &std::path::Path = #path_ref.as_ref();
+                            #read
+                        }
+                    }
+                } else {
+                    let message = format!("{} requires a path", op_str);
+                    quote! { compile_error!(#message) }
+                }
+            }
+            "spit" | "try-spit" => {
+                if args.len() == 2 {
+                    let path = args[0].to_rust();
+                    let contents = args[1].to_rust();
+                    let (path_ref, p) = (gensym("path_ref"), gensym("path"));
+                    let write = if op_str == "spit" {
+                        quote! {
+                            std::fs::write(#p, #contents)
+                                .unwrap_or_else(|e| panic!("spit: cannot write {}: {}", #p.display(), e))
+                        }
+                    } else {
+                        quote! { std::fs::write(#p, #contents) }
+                    };
+                    quote! {
+                        {
+                            let #path_ref = &(#path);
+                            let #p: &std::path::Path = #path_ref.as_ref();
+                            #write
+                        }
+                    }
+                } else {
+                    let message = format!("{} requires a path and contents", op_str);
+                    quote! { compile_error!(#message) }
+                }
+            }
 
             // Print/debug
             "println" => {
                 if args.len() == 1 {
                     let arg = args[0].to_rust();
-                    quote! { println!("{:?}", #arg) }
+                    if args[0].is_text() {
+                        quote! { println!("{}", #arg) }
+                    } else {
+                        quote! { println!("{:?}", #arg) }
+                    }
                 } else {
                     let args_tokens = args.iter().map(|e| e.to_rust());
                     quote! { println!("{:?}", (#(#args_tokens),*)) }
                 }
             }
+            "pr-str" => {
+                if args.len() == 1 {
+                    let arg = args[0].to_rust();
+                    quote! { format!("{:?}", #arg) }
+                } else {
+                    quote! { compile_error!("pr-str requires exactly 1 argument") }
+                }
+            }
+            "print-table" => {
+                if args.len() == 1 {
+                    let coll = args[0].to_rust();
+                    quote! {
+                        {
+                            let rows: Vec<String> =
+                                (#coll).iter().map(|item| format!("{:?}", item)).collect();
+                            let width = rows.len().to_string().len();
+                            for (i, row) in rows.iter().enumerate() {
+                                println!("{:>width$} | {}", i, row, width = width);
+                            }
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("print-table requires exactly 1 argument") }
+                }
+            }
+            "time" => {
+                if args.len() == 1 {
+                    let expr = args[0].to_rust();
+                    let start = gensym("start");
+                    let v = gensym("v");
+                    quote! {
+                        {
+                            let #start = std::time::Instant::now();
+                            let #v = #expr;
+                            eprintln!("Elapsed time: {:?}", #start.elapsed());
+                            #v
+                        }
+                    }
+                } else {
+                    quote! { compile_error!("time requires exactly 1 argument") }
+                }
+            }
             // Default: treat as function call
             _ => {
-                let op_ident = Ident::new(op_str, Span::call_site());
+                let op_ident = Ident::new(&rust_name(op_str), Span::call_site());
                 let args_tokens = args.iter().map(|e| e.to_rust());
                 quote! { #op_ident(#(#args_tokens),*) }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn span_points_into_source() {
+        let forms = LispExpr::parse_program("(+ total 1)").unwrap();
+        let LispExpr::List(items) = &forms[0] else {
+            panic!("expected a list, got {:?}", forms[0]);
+        };
+
+        let symbol = items[1].span();
+        assert_ne!(symbol.byte_range(), Span::call_site().byte_range());
+        assert_eq!(symbol.start().column, 3);
+        assert_eq!(items[2].span().start().column, 9);
+
+        // A vector reports the span of its first element
+        let forms = LispExpr::parse_program("[a b]").unwrap();
+        assert_eq!(forms[0].span().start().column, 1);
+    }
+
+    #[test]
+    fn hyphens_join_only_adjacent_tokens() {
+        let parse = |src: &str| format!("{:?}", LispExpr::parse_program(src).unwrap()[0]);
+        assert_eq!(parse("(list a-b)"), "List(Symbol(list), Symbol(a_b))");
+        assert_eq!(
+            parse("(chunk-by f xs)"),
+            "List(Operator(chunk-by), Symbol(f), Symbol(xs))"
+        );
+
+        // Whitespace on either side of the hyphen keeps the tokens apart
+        for src in ["(list a -b)", "(list a - b)", "(list a- b)"] {
+            let parsed = parse(src);
+            assert!(
+                parsed.starts_with("List(Symbol(list), Symbol(a), "),
+                "{}",
+                parsed
+            );
+            assert!(parsed.ends_with("Symbol(b))"), "{}", parsed);
+        }
+        assert!(!parse("(chunk - by f)").contains("chunk-by"));
+        assert_eq!(parse("(cond-> x)"), "List(Operator(cond->), Symbol(x))");
+    }
+
+    fn expand(src: &str) -> String {
+        let forms = LispExpr::parse_program(src).unwrap();
+        LispExpr::to_rust_block(&forms).to_string()
+    }
+
+    #[test]
+    fn call_arity_mismatch() {
+        let tokens = expand("(let [f (defn f [x] (* x 2))] (call f 1 2))");
+        assert!(
+            tokens.contains("compile_error ! (\"`f` takes 1 argument but was called with 2\")"),
+            "{}",
+            tokens
+        );
+
+        // Definitions in earlier top-level forms are seen too
+        let tokens = expand("(def add (defn add [a b] (+ a b))) (call add 1)");
+        assert!(tokens.contains("`add` takes 2 arguments but was called with 1"));
+
+        // Matching calls, unknown functions and bare defns, which bind no
+        // name outside themselves, are left alone
+        assert!(!expand("(let [f (defn f [x] x)] (call f 1))").contains("compile_error"));
+        assert!(!expand("(call g 1 2)").contains("compile_error"));
+        assert!(
+            !expand("(do (defn h [x] x) (defn h [x y] x) (call h 1 2))").contains("compile_error")
+        );
+    }
+
+    #[test]
+    fn call_arity_respects_shadowing() {
+        let accepted = [
+            // A binding in a sibling scope doesn't leak
+            "(do (let [f (defn f [x] x)] (call f 1)) (let [f g] (call f 1 2)))",
+            // Rebinding to a non-defn value, a parameter or a loop variable
+            "(let [f (defn f [x] x)] (let [f g] (call f 1 2)))",
+            "(let [f (defn f [x] x)] (defn outer [f] (call f 1 2)))",
+            "(let [f (defn f [x] x)] (fn [f] (call f 1 2)))",
+            "(let [f (defn f [x] x)] (closure [f g] [x] (call f x 2)))",
+            "(let [f (defn f [x] x)] (doseq [f fs] (call f 1 2)))",
+            // The innermost defn wins
+            "(let [f (defn f [x] x)] (let [f (defn f [a b] a)] (call f 1 2)))",
+        ];
+        for src in accepted {
+            assert!(!expand(src).contains("compile_error"), "{}", src);
+        }
+
+        // The outer definition is checked again once the inner scope ends
+        let tokens = expand("(let [f (defn f [x] x)] (do (let [f g] 0) (call f 1 2)))");
+        assert!(tokens.contains("`f` takes 1 argument but was called with 2"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn println_displays_text() {
+        assert_eq!(
+            expand("(println \"hello\")"),
+            "{ println ! (\"{}\" , \"hello\") }"
+        );
+        assert!(expand("(println (str \"n = \" 1))").contains("println ! (\"{}\""));
+        assert!(expand("(println [1 2])").contains("println ! (\"{:?}\""));
+        assert!(expand("(println x)").contains("println ! (\"{:?}\""));
+    }
+
+    #[test]
+    fn literal_suffixes_are_preserved() {
+        assert!(expand("(+ 5i64 3i64)").contains("(5i64) + (3i64)"));
+        assert!(expand("(* 3.0f32 x)").contains("3.0f32"));
+        assert!(expand("(-5i64)").contains("- (5i64)"));
+    }
+
+    #[test]
+    fn variadic_folds_expand_linearly() {
+        fn count_tokens(tokens: TokenStream) -> usize {
+            tokens
+                .into_iter()
+                .map(|tree| match tree {
+                    proc_macro2::TokenTree::Group(group) => 1 + count_tokens(group.stream()),
+                    _ => 1,
+                })
+                .sum()
+        }
+        let expansion_size = |op: &str, n: usize| {
+            let operands = (1..=n).map(|i| i.to_string()).collect::<Vec<_>>();
+            let forms =
+                LispExpr::parse_program(&format!("({} {})", op, operands.join(" "))).unwrap();
+            count_tokens(LispExpr::to_rust_block(&forms))
+        };
+
+        // Each further batch of operands adds the same number of tokens, so
+        // the expansion grows linearly rather than rewrapping earlier terms
+        for op in ["+", "-", "*", "/", "and", "or", "min", "max"] {
+            let sizes = [500, 1_000, 1_500].map(|n| expansion_size(op, n));
+            assert_eq!(
+                sizes[2] - sizes[1],
+                sizes[1] - sizes[0],
+                "{}: {:?}",
+                op,
+                sizes
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn mismatched_literal_branches() {
+        let tokens = expand("(if c 1 \"two\")");
+        assert!(
+            tokens.contains(
+                "if branches must have the same type, but found integer `1` and string `\\\"two\\\"`"
+            ),
+            "{}",
+            tokens
+        );
+        assert!(expand("(case n 1 true 2 3.5 false)").contains(
+            "case branches must have the same type, but found boolean `true` and float `3.5`"
+        ));
+        assert!(
+            expand("(condp = n 1 'a' \"b\")").contains("condp branches must have the same type")
+        );
+
+        // Matching literals and non-literal branches are left to rustc
+        assert!(!expand("(if c 1 2)").contains("compile_error"));
+        assert!(!expand("(if c 1 x)").contains("compile_error"));
+        assert!(!expand("(case n 1 \"a\" (str n))").contains("compile_error"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn take_nth_rejects_literal_zero() {
+        assert!(expand("(take-nth 0 [1 2 3])").contains("take-nth step must be positive"));
+        assert!(!expand("(take-nth 2 [1 2 3])").contains("compile_error"));
+    }
+
+    #[test]
+    #[cfg(not(feature = "std"))]
+    fn std_and_alloc_forms_fail_without_std() {
+        for form in STD_FORMS {
+            let tokens = expand(&format!("({} x)", form));
+            let message = format!(
+                "compile_error ! (\"{} requires std and is unavailable without the std feature\")",
+                form
+            );
+            assert!(tokens.contains(&message), "{}", tokens);
+        }
+        for form in ALLOC_FORMS {
+            let tokens = expand(&format!("({} x)", form));
+            let message = format!(
+                "compile_error ! (\"{} builds a Vec, which needs alloc and is unavailable without the std feature\")",
+                form
+            );
+            assert!(tokens.contains(&message), "{}", tokens);
+        }
+        assert!(expand("[1 2]").contains("vector literals build a Vec"));
+
+        // Forms that only need core still expand
+        assert!(
+            !expand("(let [y (* x 2)] (if (> y 10) (max x y) (abs y)))").contains("compile_error")
+        );
+    }
+
+    #[test]
+    fn visit_and_fold() {
+        let forms = LispExpr::parse_program("(+ (* 2 3) [1 (inc x)])").unwrap();
+
+        let mut lists = 0;
+        forms[0].visit(&mut |expr| {
+            if let LispExpr::List(_) = expr {
+                lists += 1;
+            }
+        });
+        assert_eq!(lists, 3);
+
+        // Rename every `x` to `y`
+        let renamed = forms[0].clone().fold(&mut |expr| match expr {
+            LispExpr::Symbol(ident) if ident == "x" => {
+                LispExpr::Symbol(Ident::new("y", ident.span()))
+            }
+            other => other,
+        });
+        assert_eq!(
+            format!("{:?}", renamed),
+            format!("{:?}", forms[0]).replace("Symbol(x)", "Symbol(y)")
+        );
+    }
+}
@@ -0,0 +1,410 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::{EvalError, LispExpr};
+
+/// A runtime value in the BigLisp language.
+///
+/// While `LispExpr` models BigLisp source code, `LispValue` models the data
+/// that code operates on at runtime. It is the currency used when embedding
+/// BigLisp in a host program: Rust values are converted into `LispValue`s
+/// before being handed to the interpreter.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LispValue {
+    /// The absence of a value, produced by forms such as `println`.
+    Nil,
+
+    /// A boolean value (`true` or `false`).
+    Bool(bool),
+
+    /// An integer value. All Rust integer types are widened to `i64`.
+    Int(i64),
+
+    /// A floating-point value.
+    Float(f64),
+
+    /// An owned string value.
+    Str(String),
+
+    /// A vector of values, used for collections.
+    Vector(Vec<LispValue>),
+
+    /// A function created by `defn` in the interpreter.
+    Function(Rc<Lambda>),
+
+    /// A native Rust function registered by the host program.
+    NativeFunction(Rc<NativeFunction>),
+}
+
+/// The signature of host functions callable from BigLisp scripts.
+pub type NativeFn = dyn Fn(&[LispValue]) -> Result<LispValue, EvalError>;
+
+/// A host-provided Rust function, registered with `Interpreter::register_fn`.
+pub struct NativeFunction {
+    pub(crate) name: String,
+    pub(crate) func: Box<NativeFn>,
+}
+
+impl NativeFunction {
+    /// Returns the name the function was registered under.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl std::fmt::Debug for NativeFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NativeFunction({})", self.name)
+    }
+}
+
+/// Native functions compare by identity, like lambdas.
+impl PartialEq for NativeFunction {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+/// A user-defined function produced by evaluating `defn`.
+///
+/// The function captures the local bindings visible where it was defined, so
+/// it can be returned from a `let` and called later.
+pub struct Lambda {
+    pub(crate) name: String,
+    pub(crate) params: Vec<String>,
+    pub(crate) body: Vec<LispExpr>,
+    pub(crate) captured: HashMap<String, LispValue>,
+}
+
+impl Lambda {
+    /// Returns the name the function was defined with.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the number of parameters the function expects.
+    pub fn arity(&self) -> usize {
+        self.params.len()
+    }
+}
+
+impl std::fmt::Debug for Lambda {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Lambda({}/{})", self.name, self.params.len())
+    }
+}
+
+/// Functions compare by identity: two lambdas are equal only if they are the
+/// same definition.
+impl PartialEq for Lambda {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl std::fmt::Display for LispValue {
+    /// Formats a value the way BigLisp source would write it, except that
+    /// strings are shown without quotes so `println` output reads naturally.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LispValue::Nil => write!(f, "nil"),
+            LispValue::Bool(b) => write!(f, "{}", b),
+            LispValue::Int(n) => write!(f, "{}", n),
+            LispValue::Float(n) => write!(f, "{:?}", n),
+            LispValue::Str(s) => write!(f, "{}", s),
+            LispValue::Vector(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            LispValue::Function(lambda) => write!(f, "#<fn {}>", lambda.name),
+            LispValue::NativeFunction(native) => write!(f, "#<native fn {}>", native.name),
+        }
+    }
+}
+
+impl LispValue {
+    /// Converts a Rust value into a `LispValue`.
+    ///
+    /// This is a convenience wrapper around [`IntoLispValue::into_lisp_value`]
+    /// that reads naturally at call sites.
+    ///
+    /// # Example
+    /// ```rust
+    /// use biglisp_core::LispValue;
+    /// assert_eq!(LispValue::from_rust(42), LispValue::Int(42));
+    /// ```
+    pub fn from_rust<T: IntoLispValue>(value: T) -> Self {
+        value.into_lisp_value()
+    }
+
+    /// Returns the BigLisp name of this value's type, used in error messages.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            LispValue::Nil => "nil",
+            LispValue::Bool(_) => "bool",
+            LispValue::Int(_) => "int",
+            LispValue::Float(_) => "float",
+            LispValue::Str(_) => "string",
+            LispValue::Vector(_) => "vector",
+            LispValue::Function(_) | LispValue::NativeFunction(_) => "function",
+        }
+    }
+
+    /// Returns the integer if this is an `Int`.
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            LispValue::Int(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns the float if this is a `Float`.
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            LispValue::Float(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean if this is a `Bool`.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            LispValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns the string slice if this is a `Str`.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            LispValue::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements if this is a `Vector`.
+    pub fn as_vec(&self) -> Option<&[LispValue]> {
+        match self {
+            LispValue::Vector(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+/// Error returned when a `LispValue` cannot be extracted as the requested Rust type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeMismatch {
+    /// The BigLisp type the caller asked for.
+    pub expected: &'static str,
+    /// The BigLisp type that was actually found.
+    pub found: &'static str,
+}
+
+impl std::fmt::Display for TypeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected {}, found {}", self.expected, self.found)
+    }
+}
+
+impl std::error::Error for TypeMismatch {}
+
+impl TypeMismatch {
+    fn new(expected: &'static str, found: &LispValue) -> Self {
+        TypeMismatch {
+            expected,
+            found: found.type_name(),
+        }
+    }
+}
+
+/// Conversion of host Rust values into BigLisp runtime values.
+///
+/// Implemented for the primitive types BigLisp understands and for vectors of
+/// any convertible type, so host programs can seed the interpreter with data.
+pub trait IntoLispValue {
+    /// Consumes `self` and produces the equivalent `LispValue`.
+    fn into_lisp_value(self) -> LispValue;
+}
+
+impl IntoLispValue for LispValue {
+    fn into_lisp_value(self) -> LispValue {
+        self
+    }
+}
+
+impl IntoLispValue for i32 {
+    fn into_lisp_value(self) -> LispValue {
+        LispValue::Int(self as i64)
+    }
+}
+
+impl IntoLispValue for i64 {
+    fn into_lisp_value(self) -> LispValue {
+        LispValue::Int(self)
+    }
+}
+
+impl IntoLispValue for f64 {
+    fn into_lisp_value(self) -> LispValue {
+        LispValue::Float(self)
+    }
+}
+
+impl IntoLispValue for bool {
+    fn into_lisp_value(self) -> LispValue {
+        LispValue::Bool(self)
+    }
+}
+
+impl IntoLispValue for String {
+    fn into_lisp_value(self) -> LispValue {
+        LispValue::Str(self)
+    }
+}
+
+impl IntoLispValue for &str {
+    fn into_lisp_value(self) -> LispValue {
+        LispValue::Str(self.to_string())
+    }
+}
+
+impl<T: IntoLispValue> IntoLispValue for Vec<T> {
+    fn into_lisp_value(self) -> LispValue {
+        LispValue::Vector(
+            self.into_iter()
+                .map(IntoLispValue::into_lisp_value)
+                .collect(),
+        )
+    }
+}
+
+impl TryFrom<LispValue> for i64 {
+    type Error = TypeMismatch;
+
+    fn try_from(value: LispValue) -> Result<Self, Self::Error> {
+        value
+            .as_int()
+            .ok_or_else(|| TypeMismatch::new("int", &value))
+    }
+}
+
+impl TryFrom<LispValue> for f64 {
+    type Error = TypeMismatch;
+
+    fn try_from(value: LispValue) -> Result<Self, Self::Error> {
+        value
+            .as_float()
+            .ok_or_else(|| TypeMismatch::new("float", &value))
+    }
+}
+
+impl TryFrom<LispValue> for bool {
+    type Error = TypeMismatch;
+
+    fn try_from(value: LispValue) -> Result<Self, Self::Error> {
+        value
+            .as_bool()
+            .ok_or_else(|| TypeMismatch::new("bool", &value))
+    }
+}
+
+impl TryFrom<LispValue> for String {
+    type Error = TypeMismatch;
+
+    fn try_from(value: LispValue) -> Result<Self, Self::Error> {
+        match value {
+            LispValue::Str(s) => Ok(s),
+            other => Err(TypeMismatch::new("string", &other)),
+        }
+    }
+}
+
+impl<T: TryFrom<LispValue, Error = TypeMismatch>> TryFrom<LispValue> for Vec<T> {
+    type Error = TypeMismatch;
+
+    fn try_from(value: LispValue) -> Result<Self, Self::Error> {
+        match value {
+            LispValue::Vector(items) => items.into_iter().map(T::try_from).collect(),
+            other => Err(TypeMismatch::new("vector", &other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primitives_into_lisp_value() {
+        assert_eq!(5i32.into_lisp_value(), LispValue::Int(5));
+        assert_eq!(LispValue::from_rust(7i64), LispValue::Int(7));
+        assert_eq!(LispValue::from_rust(1.5), LispValue::Float(1.5));
+        assert_eq!(LispValue::from_rust(true), LispValue::Bool(true));
+        assert_eq!(LispValue::from_rust("hi"), LispValue::Str("hi".to_string()));
+    }
+
+    #[test]
+    fn string_into_lisp_value() {
+        let value = String::from("hello").into_lisp_value();
+        assert_eq!(value, LispValue::Str("hello".to_string()));
+    }
+
+    #[test]
+    fn vec_into_lisp_value() {
+        let value = vec![1, 2, 3].into_lisp_value();
+        assert_eq!(
+            value,
+            LispValue::Vector(vec![
+                LispValue::Int(1),
+                LispValue::Int(2),
+                LispValue::Int(3)
+            ])
+        );
+
+        let nested = LispValue::from_rust(vec![vec!["a"], vec![]]);
+        assert_eq!(
+            nested,
+            LispValue::Vector(vec![
+                LispValue::Vector(vec![LispValue::Str("a".to_string())]),
+                LispValue::Vector(vec![]),
+            ])
+        );
+    }
+
+    #[test]
+    fn extract_int() {
+        let n: i64 = LispValue::Int(42).try_into().unwrap();
+        assert_eq!(n, 42);
+        assert_eq!(LispValue::Int(3).as_int(), Some(3));
+    }
+
+    #[test]
+    fn extract_vec() {
+        let items: Vec<i64> = LispValue::from_rust(vec![1, 2, 3]).try_into().unwrap();
+        assert_eq!(items, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn extract_type_mismatch() {
+        let err = i64::try_from(LispValue::Str("nope".to_string())).unwrap_err();
+        assert_eq!(
+            err,
+            TypeMismatch {
+                expected: "int",
+                found: "string"
+            }
+        );
+        assert_eq!(err.to_string(), "expected int, found string");
+
+        // A single bad element fails the whole vector
+        let mixed = LispValue::Vector(vec![LispValue::Int(1), LispValue::Bool(true)]);
+        let err = Vec::<i64>::try_from(mixed).unwrap_err();
+        assert_eq!(err.found, "bool");
+    }
+}
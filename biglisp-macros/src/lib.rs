@@ -1,13 +1,15 @@
 use biglisp_core::LispExpr;
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::parse_macro_input;
+use syn::{parse_macro_input, Ident, Token};
 
 /// A procedural macro that allows embedding Lisp-like expressions in Rust code.
 ///
 /// # Syntax
-/// - Supports variable capture by parsing a list of variables followed by a Lisp expression.
-/// - If variable capture is not used, it falls back to parsing a regular Lisp expression.
+/// - Supports variable capture by parsing a list of variables followed by Lisp expressions.
+/// - If variable capture is not used, it falls back to parsing regular Lisp expressions.
+/// - Several consecutive forms are evaluated in order like a `do` block, returning the
+///   value of the last one; `(def name value)` binds `name` for the forms after it.
 ///
 /// # Example
 /// ```rust
@@ -16,60 +18,127 @@ use syn::parse_macro_input;
 /// let y = 20;
 /// lisp!([x, y] (+ x y));
 /// lisp!((+ 1 2));
+/// assert_eq!(lisp!((def a 1) (def b 2) (+ a b)), 3);
 /// ```
 #[proc_macro]
 pub fn lisp(input: TokenStream) -> TokenStream {
-    use syn::{parse::Parse, Ident, Token};
-
-    // Define a structure to parse Lisp expressions with variable capture.
-    struct LispWithVars {
-        vars: Vec<Ident>, // List of variable identifiers.
-        expr: LispExpr,   // The Lisp expression.
+    // Attempt to parse the input as a `LispWithVars` structure.
+    if let Ok(parsed) = syn::parse::<LispWithVars>(input.clone()) {
+        return parsed.to_rust().into();
     }
 
-    // Implement the `Parse` trait for `LispWithVars` to define how it is parsed.
-    impl Parse for LispWithVars {
-        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-            // Parse a bracketed list of variables (e.g., [var1, var2, var3]).
-            let content;
-            syn::bracketed!(content in input);
-            let mut vars = Vec::new();
-
-            // Parse identifiers separated by commas.
-            while !content.is_empty() {
-                vars.push(content.parse::<Ident>()?);
-                if !content.is_empty() {
-                    content.parse::<Token![,]>()?;
-                }
-            }
+    // If parsing as `LispWithVars` fails, fall back to parsing regular Lisp expressions.
+    let forms = parse_macro_input!(input as LispForms);
+    let expanded = forms.to_rust(); // Convert the Lisp expressions to Rust code.
+    expanded.into()
+}
+
+/// A procedural macro that evaluates Lisp expressions with explicitly captured variables.
+///
+/// Unlike `lisp!`, the leading variable list is required, so a malformed list is
+/// reported as an error instead of being reinterpreted as a vector literal.
+///
+/// # Example
+/// ```rust
+/// use biglisp_macros::lisp_with_vars;
+/// let x = 10;
+/// let y = 20;
+/// assert_eq!(lisp_with_vars!([x, y] (+ x y)), 30);
+/// ```
+#[proc_macro]
+pub fn lisp_with_vars(input: TokenStream) -> TokenStream {
+    let parsed = parse_macro_input!(input as LispWithVars); // Parse the variables and expressions.
+    parsed.to_rust().into()
+}
 
-            // Parse the Lisp expression following the variable list.
-            let expr: LispExpr = input.parse()?;
+/// A list of captured variables followed by Lisp expressions, e.g. `[x, y] (+ x y)`.
+struct LispWithVars {
+    vars: Vec<Ident>, // List of variable identifiers.
+    forms: LispForms, // The Lisp expressions.
+}
 
-            Ok(LispWithVars { vars, expr })
+// Implement the `Parse` trait for `LispWithVars` to define how it is parsed.
+impl syn::parse::Parse for LispWithVars {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // Parse a bracketed list of variables (e.g., [var1, var2, var3]).
+        let content;
+        syn::bracketed!(content in input);
+        let mut vars = Vec::new();
+
+        // Parse identifiers separated by commas.
+        while !content.is_empty() {
+            vars.push(content.parse::<Ident>()?);
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
         }
+
+        // Parse the Lisp expressions following the variable list.
+        let forms: LispForms = input.parse()?;
+
+        Ok(LispWithVars { vars, forms })
     }
+}
 
-    // Attempt to parse the input as a `LispWithVars` structure.
-    if let Ok(parsed) = syn::parse::<LispWithVars>(input.clone()) {
-        let vars = &parsed.vars; // Extract the parsed variables.
-        let expr_tokens = parsed.expr.to_rust(); // Convert the Lisp expression to Rust code.
+impl LispWithVars {
+    /// Generates Rust code that captures the variables and evaluates the expressions.
+    fn to_rust(&self) -> proc_macro2::TokenStream {
+        let vars = &self.vars; // Extract the parsed variables.
+        let expr_tokens = self.forms.to_rust(); // Convert the Lisp expressions to Rust code.
 
-        // Generate Rust code that captures the variables and evaluates the expression.
-        return quote! {
+        quote! {
             {
                 // Capture the variables in the current scope.
                 #(let #vars = #vars;)*
                 #expr_tokens
             }
         }
-            .into();
     }
+}
 
-    // If parsing as `LispWithVars` fails, fall back to parsing a regular Lisp expression.
-    let expr = parse_macro_input!(input as LispExpr);
-    let expanded = expr.to_rust(); // Convert the Lisp expression to Rust code.
-    expanded.into()
+/// One or more consecutive Lisp expressions, as accepted by `lisp!`.
+struct LispForms(Vec<LispExpr>);
+
+impl syn::parse::Parse for LispForms {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // Parse expressions until the input is exhausted, requiring at least one.
+        let mut forms = vec![input.parse::<LispExpr>()?];
+        while !LispExpr::skip_discarded(input)?.is_empty() {
+            forms.push(input.parse()?);
+        }
+        Ok(LispForms(forms))
+    }
+}
+
+impl LispForms {
+    /// A single form expands as-is; several are wrapped in an implicit `do` block.
+    fn to_rust(&self) -> proc_macro2::TokenStream {
+        match self.0.as_slice() {
+            [expr] => expr.to_rust(),
+            forms => LispExpr::to_rust_block(forms),
+        }
+    }
+}
+
+/// A procedural macro that expands Lisp forms into statements at the call site.
+///
+/// Where `lisp!` produces a single expression (several forms become a block
+/// returning the last value), `lisp_block!` emits one statement per form and
+/// no value, so it is used in statement position inside a surrounding Rust
+/// block. The forms of a top-level `(do ...)` are spliced in rather than
+/// nested, and each `(def name value)` becomes a `let` that later Rust code in
+/// the same block can use.
+///
+/// # Example
+/// ```rust
+/// use biglisp_macros::lisp_block;
+/// lisp_block!((def a 1) (do (def b (+ a 1))));
+/// assert_eq!(a + b, 3);
+/// ```
+#[proc_macro]
+pub fn lisp_block(input: TokenStream) -> TokenStream {
+    let forms = parse_macro_input!(input as LispForms);
+    LispExpr::to_rust_statements(&forms.0).into()
 }
 
 /// A helper procedural macro for testing Lisp expressions.
@@ -91,4 +160,23 @@ pub fn lisp_fn(input: TokenStream) -> TokenStream {
         }
     }
         .into()
-}
\ No newline at end of file
+}
+/// A procedural macro that declares a BigLisp `defn` or `def` as a Rust item.
+///
+/// Unlike `lisp!`, which produces a closure, this can be used at module level
+/// and the function may call itself. A docstring after the parameter vector
+/// becomes the function's doc comment. `(def NAME value)` declares a constant.
+/// A multi-arity `(defn f ([x] ...) ([x y] ...))` declares `f_1`, `f_2`, ...
+///
+/// # Example
+/// ```rust
+/// use biglisp_macros::lisp_item;
+/// lisp_item!((defn square [x] "Squares its argument." (* x x)));
+/// lisp_item!((def SIDE 4));
+/// assert_eq!(square(SIDE), 16);
+/// ```
+#[proc_macro]
+pub fn lisp_item(input: TokenStream) -> TokenStream {
+    let expr = parse_macro_input!(input as LispExpr); // Parse the input as a Lisp expression.
+    expr.to_rust_item().into()
+}
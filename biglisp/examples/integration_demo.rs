@@ -90,8 +90,8 @@ fn main() {
     println!("  (if (> 5 3) \"yes\" \"no\") = {}", cond1);
     println!("  (if (= 2 3) 100 200) = {}", cond2);
     println!(
-        "  Age {}: {} = {}",
-        user_age, "(if (> user_age 18) \"adult\" \"minor\")", adult_check
+        "  Age {}: (if (> user_age 18) \"adult\" \"minor\") = {}",
+        user_age, adult_check
     );
     println!();
 
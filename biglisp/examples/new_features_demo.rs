@@ -118,7 +118,7 @@ fn main() {
     let score = 87;
     let threshold = 85;
 
-    let age_check = lisp!([age] (and (gte age 18) (lte age 65)));
+    let age_check = lisp!([age] (between? age 18 65));
     let score_check = lisp!([score, threshold] (gte score threshold));
     let bonus_check = lisp!([score] (and (even score) (gte score 80)));
 
@@ -177,8 +177,7 @@ fn main() {
     // Example 2: Data validation
     let user_input = 42;
     let validation = lisp!([user_input] (
-        and (gte user_input 1)
-            (lte user_input 100)
+        and (between? user_input 1 100)
             (ne user_input 13)
             (pos user_input)
     ));
@@ -202,10 +201,8 @@ fn main() {
     let temperature = 72;
     let humidity = 45;
     let comfort_zone = lisp!([temperature, humidity] (
-        and (gte temperature 68)
-            (lte temperature 78)
-            (gte humidity 30)
-            (lte humidity 60)
+        and (between? temperature 68 78)
+            (between? humidity 30 60)
     ));
 
     println!("  Comfort Zone:");
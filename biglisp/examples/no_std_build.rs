@@ -0,0 +1,26 @@
+// no_std Build Check
+// Compiles the std-free subset of BigLisp inside a `#![no_std]` crate:
+//   cargo build -p biglisp --no-default-features --target thumbv7em-none-eabihf --example no_std_build
+// (or `make check-no-std`)
+#![no_std]
+
+use biglisp::prelude::*;
+
+/// Arithmetic and math utilities.
+pub fn arithmetic(x: i32, y: i32) -> i32 {
+    lisp!([x, y] (+ (* x 2) (- y 1) (% x 3) (abs (- y)) (max x y) (gcd x y)))
+}
+
+/// Comparisons and boolean logic.
+pub fn comparisons(x: i32, y: i32) -> bool {
+    lisp!([x, y] (and (gte x 0) (or (< x y) (zero y)) (not (= x 7))))
+}
+
+/// Control flow and local bindings.
+pub fn control_flow(x: i32) -> i32 {
+    lisp!([x]
+        (let [doubled (* x 2)]
+          (if (> doubled 10)
+            (case x 6 60 7 70 0)
+            doubled)))
+}
@@ -1,13 +1,23 @@
-pub use biglisp_macros::lisp;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use biglisp_macros::{lisp, lisp_block, lisp_item, lisp_with_vars};
 pub mod guts {
-    pub use biglisp_core::LispExpr;
+    #[cfg(feature = "std")]
+    pub use biglisp_core::{builtins, Arity, Builtin, LispExpr};
     pub use biglisp_macros::lisp_fn;
 }
+#[cfg(feature = "std")]
+pub mod runtime {
+    pub use biglisp_core::{
+        EvalError, Interpreter, IntoLispValue, Lambda, LispValue, NativeFn, NativeFunction,
+        TypeMismatch, DEFAULT_MAX_CALL_DEPTH,
+    };
+}
 pub mod prelude {
-    pub use crate::lisp;
+    pub use crate::{lisp, lisp_block, lisp_item, lisp_with_vars};
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
 
@@ -85,37 +95,37 @@ mod tests {
     fn comparison_operations() {
         // Test equality
         let result_eq_true = lisp!((= 5 5));
-        assert_eq!(result_eq_true, true);
+        assert!(result_eq_true);
 
         let result_eq_false = lisp!((= 3 7));
-        assert_eq!(result_eq_false, false);
+        assert!(!result_eq_false);
 
         // Test less than
         let result_lt_true = lisp!((< 3 7));
-        assert_eq!(result_lt_true, true);
+        assert!(result_lt_true);
 
         let result_lt_false = lisp!((< 7 3));
-        assert_eq!(result_lt_false, false);
+        assert!(!result_lt_false);
 
         // Test greater than
         let result_gt_true = lisp!((> 7 3));
-        assert_eq!(result_gt_true, true);
+        assert!(result_gt_true);
 
         let result_gt_false = lisp!((> 3 7));
-        assert_eq!(result_gt_false, false);
+        assert!(!result_gt_false);
     }
 
     #[test]
     fn nested_comparisons() {
         // Test comparisons with nested arithmetic
         let result_nested_eq = lisp!((= (+ 2 3) (* 1 5)));
-        assert_eq!(result_nested_eq, true);
+        assert!(result_nested_eq);
 
         let result_nested_lt = lisp!((< (+ 1 2) (* 2 3)));
-        assert_eq!(result_nested_lt, true);
+        assert!(result_nested_lt);
 
         let result_complex_comp = lisp!((> (+ (* 2 3) 1) (- 10 3)));
-        assert_eq!(result_complex_comp, false); // 7 > 7 is false
+        assert!(!result_complex_comp); // 7 > 7 is false
     }
 
     #[test]
@@ -137,6 +147,10 @@ mod tests {
         let result_truncated = lisp!((/ 7 2));
         assert_eq!(result_truncated, 3); // Integer division truncates
 
+        // Sums and products start from their first operand, so floats work
+        assert_eq!(lisp!((+ 1.5 2.5 1.0)), 5.0);
+        assert_eq!(lisp!((* 1.5 2.0 2.0)), 6.0);
+
         let result_chained = lisp!((/ 60 3 2));
         assert_eq!(result_chained, 10); // 60 / 3 / 2 = 20 / 2 = 10
     }
@@ -157,6 +171,27 @@ mod tests {
         assert_eq!(result_with_negation, 5); // -5 + 10 = 5
     }
 
+    fn type_of<T>(_: &T) -> &'static str {
+        std::any::type_name::<T>()
+    }
+
+    #[test]
+    fn literal_suffixes() {
+        // Suffixes survive parsing, so they decide the type of the result
+        let sum = lisp!((+ 5i64 3i64));
+        assert_eq!(sum, 8);
+        assert_eq!(type_of(&sum), "i64");
+        assert_eq!(type_of(&lisp!((* 2u8 3))), "u8");
+        assert_eq!(type_of(&lisp!((- 3.0f32 1.5f32))), "f32");
+        assert_eq!(type_of(&lisp!((+ 1 2))), "i32");
+
+        // `-5i64` is unary negation of the suffixed literal
+        let negative = lisp!((-5i64));
+        assert_eq!(negative, -5);
+        assert_eq!(type_of(&negative), "i64");
+        assert_eq!(type_of(&lisp!((+ (- 5i64) 1))), "i64");
+    }
+
     #[test]
     fn conditional_expressions() {
         // Test if expressions
@@ -175,6 +210,21 @@ mod tests {
         assert_eq!(result_nested, 10); // (2+3) > 4 is true, so 2*5 = 10
     }
 
+    #[test]
+    fn if_first_and_when_first() {
+        assert_eq!(lisp!((if-first [x [7 8 9]] (* x 10) (- 1))), 70);
+
+        // Unlike `first`, an empty collection takes the else branch
+        let empty: Vec<i32> = Vec::new();
+        assert_eq!(lisp!((if-first [x empty] (* x 10) (- 1))), -1);
+
+        let seen = std::cell::Cell::new(0);
+        let record = |x: i32| seen.set(x);
+        let _: () = lisp!((when-first [x [5 6]] (call record x)));
+        lisp!((when-first [x empty] (call record x)));
+        assert_eq!(seen.get(), 5);
+    }
+
     #[test]
     fn local_bindings() {
         // Test let expressions with local variable bindings
@@ -223,11 +273,65 @@ mod tests {
         lisp!((println (+ 2 3)));
         lisp!((println (+ 1 2) (* 3 4)));
 
-        // Test that println returns unit type (implicitly)
-        let _unit_result = lisp!((println "test output"));
+        // Test that println returns unit type
+        let _unit_result: () = lisp!((println "test output"));
+    }
+
+    #[test]
+    fn pr_str_debug_form() {
+        assert_eq!(lisp!((pr-str [1 2 3])), "[1, 2, 3]");
+        assert_eq!(lisp!((pr-str "hi")), "\"hi\"");
+        assert_eq!(lisp!((str "got " (pr-str (list 1 2)))), "got [1, 2]");
+    }
+
+    #[test]
+    fn print_table_output() {
+        let _unit: () = lisp!((print-table [1 2 3]));
+
+        // Any collection with `iter()` works, and it is only borrowed
+        let names = ["ann", "bob"];
+        let _unit: () = lisp!((print-table names));
+        assert_eq!(names.len(), 2);
+    }
+
+    #[test]
+    fn environment_variables() {
+        let missing = "BIGLISP_TEST_SURELY_UNSET_VARIABLE";
+        assert_eq!(lisp!((env missing)), None);
+        assert_eq!(lisp!((env missing "fallback")), "fallback");
+
+        // Cargo sets this for every test binary
+        assert_eq!(lisp!((env "CARGO_PKG_NAME")), Some("biglisp".to_string()));
+    }
+
+    #[test]
+    fn read_line_compiles() {
+        // Reading stdin can't be exercised here, so only check the form compiles
+        // to a `String`-producing closure. Manual check: print
+        // `lisp!((read-line))` from a binary and pipe `"  hello \n"` into it;
+        // it prints `hello`.
+        let prompt = || -> String { lisp!((read-line)) };
+        let _ = prompt;
+    }
 
-        // These tests verify println compiles and runs without panicking
-        assert!(true);
+    #[test]
+    fn file_round_trip() {
+        let path = std::env::temp_dir().join(format!("biglisp-spit-{}.txt", std::process::id()));
+        let _: () = lisp!((spit path (str "hello " 42)));
+        assert_eq!(lisp!((slurp path)), "hello 42");
+
+        // The Result variants report errors instead of panicking
+        assert!(lisp!((try-spit path "again")).is_ok());
+        assert_eq!(lisp!((try-slurp path)).unwrap(), "again");
+        std::fs::remove_file(&path).unwrap();
+        assert!(lisp!((try-slurp path)).is_err());
+        assert!(std::panic::catch_unwind(|| lisp!((slurp "/nonexistent/biglisp.txt"))).is_err());
+    }
+
+    #[test]
+    fn time_returns_inner_value() {
+        assert_eq!(lisp!((time (+ 1 2 3))), 6);
+        assert_eq!(lisp!((time (dotimes-sum i 100 i))), 4950);
     }
 
     #[allow(unused_must_use)]
@@ -251,6 +355,71 @@ mod tests {
         assert_eq!(result2, 25);
     }
 
+    #[test]
+    fn explicit_variable_capture() {
+        let x = 5;
+        let y = 10;
+        assert_eq!(lisp_with_vars!([x, y] (+ x y)), 15);
+        assert_eq!(lisp_with_vars!([x] (def z (* x 2)) (+ x z)), 15);
+
+        // Capturing nothing is allowed
+        assert_eq!(lisp_with_vars!([] (* 6 7)), 42);
+    }
+
+    #[test]
+    fn with_vars_renames() {
+        let x = 5;
+        let y = 2;
+        assert_eq!(lisp!((with-vars [x as base] (+ base 1))), 6);
+        assert_eq!(lisp!((with-vars [x as base y] (* base y))), 10);
+
+        // A rename frees the original name for a local binding
+        assert_eq!(lisp!((with-vars [x as outer] (let [x 100] (+ x outer)))), 105);
+    }
+
+    #[test]
+    fn multiple_top_level_forms() {
+        assert_eq!(lisp!((def a 1) (def b 2) (+ a b)), 3);
+
+        // Earlier forms run for their effects, the last one is the result
+        let counter = std::cell::Cell::new(0);
+        let bump = || counter.set(counter.get() + 1);
+        let total = lisp!((call bump) (call bump) (+ 40 2));
+        assert_eq!(total, 42);
+        assert_eq!(counter.get(), 2);
+
+        // def also works inside do, and captured variables combine with forms
+        assert_eq!(lisp!((do (def total-count 5) (* total-count 2))), 10);
+        let x = 3;
+        assert_eq!(lisp!([x] (def y (* x x)) (+ x y)), 12);
+    }
+
+    #[test]
+    fn do_with_type_annotation() {
+        // Without `:as`, calling a method on the block's `{integer}` value is
+        // ambiguous and fails to compile
+        assert_eq!(lisp!((do :as i64 (def x 3) (* x 4))).pow(2), 144);
+
+        let n = lisp!((do :as u64 (+ 1 2)));
+        assert_eq!(type_of(&n), "u64");
+        assert_eq!(type_of(&lisp!((do (+ 1 2)))), "i32");
+    }
+
+    #[test]
+    fn lisp_block_statements() {
+        // Bindings made by def stay in scope for the Rust code that follows
+        let base = 10;
+        lisp_block!((def doubled (* base 2)) (do (def total-count (+ doubled 1))));
+        assert_eq!(doubled, 20);
+        assert_eq!(total_count, 21);
+
+        // Non-def forms run for their effects
+        let counter = std::cell::Cell::new(0);
+        let bump = || counter.set(counter.get() + 1);
+        lisp_block!((call bump) (do (call bump) (call bump)));
+        assert_eq!(counter.get(), 3);
+    }
+
     #[test]
     fn variable_capture() {
         // Test variable capture using unified lisp! macro with [vars] syntax
@@ -274,33 +443,86 @@ mod tests {
     fn boolean_operations() {
         // Test and operation
         let result_and_true = lisp!((and true true));
-        assert_eq!(result_and_true, true);
+        assert!(result_and_true);
 
         let result_and_false = lisp!((and true false));
-        assert_eq!(result_and_false, false);
+        assert!(!result_and_false);
 
         let result_and_multiple = lisp!((and (> 5 3) (< 2 4) (= 1 1)));
-        assert_eq!(result_and_multiple, true);
+        assert!(result_and_multiple);
 
         // Test or operation
         let result_or_true = lisp!((or false true));
-        assert_eq!(result_or_true, true);
+        assert!(result_or_true);
 
         let result_or_false = lisp!((or false false));
-        assert_eq!(result_or_false, false);
+        assert!(!result_or_false);
 
         let result_or_multiple = lisp!((or (< 5 3) (> 2 4) (= 1 1)));
-        assert_eq!(result_or_multiple, true);
+        assert!(result_or_multiple);
 
         // Test not operation
         let result_not_true = lisp!((not false));
-        assert_eq!(result_not_true, true);
+        assert!(result_not_true);
 
         let result_not_false = lisp!((not true));
-        assert_eq!(result_not_false, false);
+        assert!(!result_not_false);
 
         let result_not_expr = lisp!((not (< 5 3)));
-        assert_eq!(result_not_expr, true);
+        assert!(result_not_expr);
+    }
+
+    #[test]
+    fn xor_nand_nor() {
+        const {
+            assert!(lisp!((xor true false)));
+            assert!(!lisp!((xor true true)));
+            assert!(!lisp!((nand true true)));
+            assert!(lisp!((nand true false true)));
+            assert!(lisp!((nor false false)));
+            assert!(!lisp!((nor false true)));
+            assert!(!lisp!((nand)));
+            assert!(lisp!((nor)));
+        }
+    }
+
+    #[test]
+    fn boolean_folds_start_from_first_operand() {
+        // No `true`/`false` seed is emitted when there are operands
+        let expr: crate::guts::LispExpr = syn::parse_str("(and a b)").unwrap();
+        assert_eq!(expr.to_rust().to_string(), "(a) && (b)");
+        let expr: crate::guts::LispExpr = syn::parse_str("(or a b c)").unwrap();
+        assert_eq!(expr.to_rust().to_string(), "(a) || (b) || (c)");
+
+        // Operand types are inferred from the operands alone
+        let both = |p, q| lisp!((and p q));
+        assert!(!both(true, false));
+        let either = |p, q| lisp!((or p q));
+        assert!(either(true, false));
+
+        // The seed only appears for the empty case
+        const {
+            assert!(lisp!((and)));
+            assert!(!lisp!((or)));
+            assert!(lisp!((and (> 2 1))));
+        }
+    }
+
+    #[test]
+    fn value_returning_boolean_operations() {
+        // or-val returns the first truthy (non-default) operand
+        assert_eq!(lisp!((or-val 0 5)), 5);
+        assert_eq!(lisp!((or-val 3 5)), 3);
+        assert_eq!(lisp!((or-val 0 0)), 0);
+        assert_eq!(lisp!((or-val "" "fallback")), "fallback");
+
+        // and-val returns the first falsy operand, or the last one
+        assert_eq!(lisp!((and-val 1 2 3)), 3);
+        assert_eq!(lisp!((and-val 1 0 3)), 0);
+
+        // Default-value pattern with a captured variable
+        let configured = 0;
+        assert_eq!(lisp!([configured] (or-val configured 8080)), 8080);
     }
 
     #[test]
@@ -329,7 +551,48 @@ mod tests {
         assert_eq!(rest_result, vec![2, 3, 4]);
 
         let rest_single = lisp!((rest[42]));
-        assert_eq!(rest_single, Vec::<i32>::new());
+        assert!(rest_single.is_empty());
+
+        // Test second, third and nthrest operations
+        assert_eq!(lisp!((second [10 20 30])), 20);
+        assert_eq!(lisp!((third [10 20 30])), 30);
+        assert_eq!(lisp!((third [10 20])), 0);
+        assert_eq!(lisp!((nthrest 2 [1 2 3 4])), vec![3, 4]);
+        assert!(lisp!((nthrest 5 [1 2 3])).is_empty());
+        assert_eq!(lisp!((nthrest (- 1) [1 2 3])), vec![1, 2, 3]);
+
+        // Test take-nth operation
+        assert_eq!(lisp!((take-nth 2 [0 1 2 3 4 5])), vec![0, 2, 4]);
+        assert_eq!(lisp!((take-nth 3 [1 2 3 4])), vec![1, 4]);
+        assert_eq!(lisp!((take-nth 1 [7 8])), vec![7, 8]);
+        let step = -2;
+        let negative = std::panic::catch_unwind(|| lisp!((take-nth step [1 2 3])));
+        assert_eq!(
+            *negative.unwrap_err().downcast::<String>().unwrap(),
+            "take-nth step must be positive, got -2"
+        );
+
+        // Test split-at operation
+        let (front, back) = lisp!((split-at 2 [1 2 3 4]));
+        assert_eq!(front, vec![1, 2]);
+        assert_eq!(back, vec![3, 4]);
+        let (all, none) = lisp!((split-at 9 [1 2]));
+        assert_eq!((all, none.len()), (vec![1, 2], 0));
+        let (none, all) = lisp!((split-at (- 1) [1 2 3 4]));
+        assert_eq!((none.len(), all), (0, vec![1, 2, 3, 4]));
+
+        // Test slice operation
+        assert_eq!(lisp!((slice [1 2 3 4 5] 1 4)), vec![2, 3, 4]);
+        assert_eq!(lisp!((slice [1 2 3] 1 10)), vec![2, 3]);
+        assert!(lisp!((slice [1 2 3] 3 1)).is_empty());
+        assert_eq!(lisp!((slice [1 2 3] (- 2) 2)), vec![1, 2]);
+        assert!(lisp!((slice [1 2 3] 0 (- 1))).is_empty());
+
+        // Test last and butlast operations
+        assert_eq!(lisp!((last [1 2 3])), 3);
+        assert_eq!(lisp!((butlast [1 2 3])), vec![1, 2]);
+        assert!(lisp!((butlast [42])).is_empty());
+        assert_eq!(lisp!((butlast (rest [1 2]))), lisp!((rest [7])));
 
         // Test cons operation
         let cons_result = lisp!((cons 0 [1 2 3]));
@@ -339,6 +602,319 @@ mod tests {
         assert_eq!(cons_single, vec![0, 42]);
     }
 
+    #[test]
+    fn discarded_forms() {
+        assert_eq!(lisp!((+ 1 #_2 3)), 4);
+        assert_eq!(lisp!((+ 1 #_(* 100 100) 3)), 4);
+        assert_eq!(lisp!([1 #_2 3 #_4]), vec![1, 3]);
+        assert_eq!(lisp!((#_ignored-head + 5 5)), 10);
+
+        // Discards work at the top level of a multi-form invocation too
+        assert_eq!(lisp!(#_(def a 100) (def a 1) (+ a 1) #_(+ a 2)), 2);
+
+        let program = crate::guts::LispExpr::parse_program("(+ 1 2) #_(+ 3 4)").unwrap();
+        assert_eq!(program.len(), 1);
+    }
+
+    #[test]
+    fn hyphenated_identifiers() {
+        assert_eq!(lisp!((let [total-count 5] total-count)), 5);
+
+        let price_with_tax = lisp!((defn price-with-tax [price] (+ price (/ price 10))));
+        assert_eq!(lisp!((let [base-price 100] (call price-with-tax base-price))), 110);
+
+        // Host variables with underscores are reachable by their hyphenated name
+        let item_count = 3;
+        assert_eq!(lisp!((* item-count 2)), 6);
+
+        // Plain subtraction is unaffected
+        let a = 10;
+        let b = 4;
+        assert_eq!(lisp!((- a b)), 6);
+        assert_eq!(lisp!((- a)), -10);
+    }
+
+    #[test]
+    fn membership_tests() {
+        assert!(lisp!((contains? [1 2 3] 2)));
+        assert!(!lisp!((contains? [1 2 3] 9)));
+        assert!(lisp!((contains? ["a" "b"] "b")));
+
+        let mut ages = std::collections::HashMap::new();
+        ages.insert("ann", 31);
+        assert!(lisp!((has-key? ages "ann")));
+        assert!(!lisp!((has-key? ages "bob")));
+    }
+
+    #[test]
+    fn select_keys_from_map() {
+        let mut config = std::collections::HashMap::new();
+        config.insert("host", 1);
+        config.insert("port", 2);
+        config.insert("debug", 3);
+
+        let selected = lisp!((select-keys config ["host" "port" "missing"]));
+        assert_eq!(selected.len(), 2);
+        assert_eq!(selected["port"], 2);
+        assert!(!selected.contains_key("debug"));
+        // The source map is only borrowed
+        assert_eq!(config.len(), 3);
+    }
+
+    #[test]
+    fn merge_maps() {
+        let mut defaults = std::collections::HashMap::new();
+        defaults.insert("port", 80);
+        defaults.insert("retries", 3);
+        let mut overrides = std::collections::HashMap::new();
+        overrides.insert("port", 8080);
+
+        let merged = lisp!((merge defaults overrides));
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged["port"], 8080);
+        assert_eq!(merged["retries"], 3);
+        // The inputs are left untouched
+        assert_eq!(defaults["port"], 80);
+    }
+
+    #[test]
+    fn sorted_map_pairs() {
+        let mut scores = std::collections::HashMap::new();
+        scores.insert("cy", 3);
+        scores.insert("al", 1);
+        scores.insert("bo", 2);
+        assert_eq!(
+            lisp!((sorted-pairs scores)),
+            vec![("al", 1), ("bo", 2), ("cy", 3)]
+        );
+
+        let groups = lisp!((sorted-pairs (group-by (defn parity [x] (% x 2)) [3 1 2])));
+        assert_eq!(groups, vec![(0, vec![2]), (1, vec![3, 1])]);
+    }
+
+    #[test]
+    fn list_constructor() {
+        assert_eq!(lisp!((list 1 2 3)), vec![1, 2, 3]);
+        assert_eq!(lisp!((first (list 10 20))), 10);
+        assert_eq!(lisp!((cons 0 (list 1 2))), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn vec_of_constructor() {
+        // No surrounding annotation is needed for the empty vector
+        let empty = lisp!((vec-of i64));
+        assert!(empty.is_empty());
+        let _: Vec<i64> = empty;
+
+        let bytes = lisp!((vec-of u8 1 2 (+ 1 2)));
+        assert_eq!(bytes, vec![1u8, 2, 3]);
+        let _: Vec<u8> = bytes;
+        assert_eq!(lisp!((count (vec-of i32))), 0);
+    }
+
+    #[test]
+    fn assoc_vec_sets_index() {
+        assert_eq!(lisp!((assoc-vec [1 2 3] 1 20)), vec![1, 20, 3]);
+
+        let original = vec![7, 8];
+        let updated = lisp!((assoc-vec original 0 (* 2 5)));
+        assert_eq!(updated, vec![10, 8]);
+        assert_eq!(original, vec![7, 8]);
+
+        let out_of_bounds = std::panic::catch_unwind(|| lisp!((assoc-vec [1 2] 5 0)));
+        assert!(out_of_bounds.is_err());
+    }
+
+    #[test]
+    fn update_vector_element() {
+        let updated = lisp!((update [1 2 3] 1 (defn inc2 [x] (+ x 10))));
+        assert_eq!(updated, vec![1, 12, 3]);
+
+        let index = 2;
+        let doubled = lisp!([index] (update [5 6 7] index (defn dbl [x] (* x 2))));
+        assert_eq!(doubled, vec![5, 6, 14]);
+    }
+
+    #[test]
+    fn assoc_in_nested_vectors() {
+        let grid = vec![vec![1, 2], vec![3, 4]];
+        let updated = lisp!((assoc-in grid [1 0] 9));
+        assert_eq!(updated, vec![vec![1, 2], vec![9, 4]]);
+
+        // The original grid is left untouched
+        assert_eq!(grid, vec![vec![1, 2], vec![3, 4]]);
+
+        assert_eq!(lisp!((assoc-in [1 2 3] [2] (* 2 5))), vec![1, 2, 10]);
+    }
+
+    #[test]
+    fn vec_to_array() {
+        let array: [i32; 3] = lisp!((vec->array [1 2 3] 3));
+        assert_eq!(array, [1, 2, 3]);
+
+        let data: Vec<i32> = (4..6).collect();
+        assert_eq!(lisp!((vec->array data 2)), [4, 5]);
+
+        let wrong_length = std::panic::catch_unwind(|| lisp!((vec->array [1 2] 3)));
+        assert!(wrong_length.is_err());
+    }
+
+    #[test]
+    fn grid_helpers() {
+        assert_eq!(lisp!((count (make-grid 3 4 0))), 3);
+        assert_eq!(lisp!((make-grid 2 3 1)), vec![vec![1, 1, 1], vec![1, 1, 1]]);
+
+        // Round-trip a value through assoc-in and grid-ref
+        let grid = lisp!((assoc-in (make-grid 2 2 0) [1 0] 5));
+        assert_eq!(lisp!((grid-ref grid 1 0)), 5);
+        assert_eq!(lisp!((grid-ref grid 0 1)), 0);
+        let negative = std::panic::catch_unwind(|| lisp!((grid-ref grid 0 (- 1))));
+        assert_eq!(
+            *negative.unwrap_err().downcast::<String>().unwrap(),
+            "grid-ref: column index -1 is negative"
+        );
+
+        // Negative dimensions clamp to an empty grid or empty rows
+        assert!(lisp!((make-grid (- 2) 3 0)).is_empty());
+        assert_eq!(lisp!((make-grid 2 (- 3) 0)), vec![Vec::<i32>::new(), Vec::new()]);
+    }
+
+    #[test]
+    fn repeat_operation() {
+        assert_eq!(lisp!((repeat 3 7)), vec![7, 7, 7]);
+        assert_eq!(lisp!((count (repeat 5 0))), 5);
+        assert_eq!(lisp!((repeat 2 "ab")), vec!["ab", "ab"]);
+        assert!(lisp!((repeat (- 1) 7)).is_empty());
+    }
+
+    #[test]
+    fn chunk_by_operation() {
+        // Consecutive elements with the same key end up in the same chunk
+        let chunks = lisp!((chunk-by (defn id [x] x) [1 1 2 2 1]));
+        assert_eq!(chunks, vec![vec![1, 1], vec![2, 2], vec![1]]);
+
+        let by_parity = lisp!((chunk-by (defn parity [x] (% x 2)) [2 4 1 3 6]));
+        assert_eq!(by_parity, vec![vec![2, 4], vec![1, 3], vec![6]]);
+    }
+
+    #[test]
+    fn group_by_operation() {
+        let groups = lisp!((group-by (defn parity [x] (% x 2)) [1 2 3 4 5]));
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[&0], vec![2, 4]);
+        assert_eq!(groups[&1], vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn max_by_and_min_by() {
+        let distance = |x: i32| (x - 10).abs();
+        assert_eq!(lisp!((max-by distance [8 3 12 15])), Some(3));
+        assert_eq!(lisp!((min-by distance [8 3 12 15])), Some(8));
+
+        let square = lisp!((defn square [x] (* x x)));
+        assert_eq!(lisp!((max-by square [(- 5) 2 4])), Some(-5));
+
+        let empty: Vec<i32> = vec![];
+        assert_eq!(lisp!((max-by distance empty)), None);
+    }
+
+    #[test]
+    fn min_max_operation() {
+        assert_eq!(lisp!((min-max [3 1 4 1 5])), (1, 5));
+        assert_eq!(lisp!((min-max [7])), (7, 7));
+        assert_eq!(lisp!((min-max [2.5 (- 1.0)])), (-1.0, 2.5));
+    }
+
+    #[test]
+    fn reductions_operation() {
+        assert_eq!(lisp!((reductions (defn add [a b] (+ a b)) 0 [1 2 3])), vec![0, 1, 3, 6]);
+
+        // Running maximum
+        let running_max = |a: i32, b: i32| a.max(b);
+        assert_eq!(lisp!((reductions running_max 0 [3 1 4 1 5])), vec![0, 3, 3, 4, 4, 5]);
+
+        let empty: Vec<i32> = vec![];
+        assert_eq!(lisp!((reductions running_max 7 empty)), vec![7]);
+    }
+
+    #[test]
+    fn reduce_while_operation() {
+        // Stops adding once the running sum exceeds 10
+        let sum = lisp!((reduce-while (defn under [acc] (lte acc 10)) (defn add [a b] (+ a b)) 0 [1 2 3 4 5 6 7 8]));
+        assert_eq!(sum, 15);
+
+        // A predicate that always holds is a plain fold
+        let always = |_: i32| true;
+        let add = |a: i32, b: i32| a + b;
+        assert_eq!(lisp!((reduce-while always add 0 [1 2 3])), 6);
+
+        // A failing predicate returns the initial value untouched
+        let never = |_: i32| false;
+        assert_eq!(lisp!((reduce-while never add 42 [1 2 3])), 42);
+    }
+
+    #[test]
+    fn iterate_operation() {
+        assert_eq!(lisp!((iterate (defn dbl [x] (* x 2)) 1 4)), vec![1, 2, 4, 8]);
+
+        let collatz = |n: i32| if n % 2 == 0 { n / 2 } else { 3 * n + 1 };
+        assert_eq!(lisp!((iterate collatz 6 5)), vec![6, 3, 10, 5, 16]);
+        assert_eq!(lisp!((count (iterate collatz 6 0))), 0);
+        assert_eq!(lisp!((count (iterate collatz 6 (- 3)))), 0);
+    }
+
+    #[test]
+    fn mapcat_operation() {
+        assert_eq!(lisp!((mapcat (defn twice [n] [n n]) [1 2])), vec![1, 1, 2, 2]);
+
+        let upto = |n: i32| (0..n).collect::<Vec<_>>();
+        assert_eq!(lisp!((mapcat upto [1 0 3])), vec![0, 0, 1, 2]);
+    }
+
+    #[test]
+    fn keep_operation() {
+        let halves = lisp!((keep (defn half [n] (if (even n) (Some (/ n 2)) None)) [1 2 3 4 6]));
+        assert_eq!(halves, vec![1, 2, 3]);
+
+        let evens = |n: i32| (n % 2 == 0).then_some(n);
+        assert_eq!(lisp!((keep evens [1 2 3 4])), vec![2, 4]);
+        assert!(lisp!((keep evens [1 3])).is_empty());
+    }
+
+    #[test]
+    fn repeatedly_operation() {
+        // A counter-like host closure mutating its own state
+        let mut next_id = 0;
+        let fresh_id = move || {
+            next_id += 1;
+            next_id * 10
+        };
+        assert_eq!(lisp!((repeatedly 3 fresh_id)), vec![10, 20, 30]);
+
+        let counter = lisp!((atom 0));
+        let inc = lisp!((defn inc [x] (+ x 1)));
+        let ticks = lisp!((repeatedly 4 (fn [] (do (swap! counter inc) (deref counter)))));
+        assert_eq!(ticks, vec![1, 2, 3, 4]);
+        assert!(lisp!((repeatedly 0 (fn [] 1))).is_empty());
+        assert!(lisp!((repeatedly (- 3) (fn [] 1))).is_empty());
+    }
+
+    #[test]
+    fn find_index_operation() {
+        assert_eq!(lisp!((find-index (defn gt3 [x] (> x 3)) [1 2 3 4 5])), Some(3));
+        assert_eq!(lisp!((find-index (defn neg? [x] (< x 0)) [1 2 3])), None);
+    }
+
+    #[test]
+    fn most_common_operation() {
+        let top = lisp!((most-common 2 [1 3 2 3 2 3 4]));
+        assert_eq!(top, vec![(3, 3), (2, 2)]);
+
+        // Ties are broken by first occurrence
+        let tied = lisp!((most-common 2 [5 6 6 5 7]));
+        assert_eq!(tied, vec![(5, 2), (6, 2)]);
+    }
+
     #[test]
     fn string_operations() {
         // Test string concatenation
@@ -359,6 +935,42 @@ mod tests {
         assert_eq!(result_complex, "2 + 3 = 5 and 2 * 3 = 6");
     }
 
+    #[test]
+    fn char_literals() {
+        assert_eq!(lisp!('x'), 'x');
+        assert_eq!(lisp!((char-at "abc" 1)), 'b');
+        assert_eq!(lisp!((char-at "héllo" 2)), 'l');
+        assert_eq!(lisp!((case (char-at "yes" 0) 'y' 1 'n' 0 (- 1))), 1);
+        assert!(lisp!((= (char-at "aa" 0) (char-at "aa" 1))));
+    }
+
+    #[test]
+    fn char_code_conversions() {
+        assert_eq!(lisp!((ascii-code 'A')), 65);
+        assert_eq!(lisp!((code->char 66)), 'B');
+        // Shift a letter, Caesar-cipher style
+        assert_eq!(lisp!((code->char (+ (ascii-code 'a') 3))), 'd');
+    }
+
+    #[test]
+    fn number_bases() {
+        assert_eq!(lisp!((hex 255)), "0xff");
+        assert_eq!(lisp!((bin 5)), "0b101");
+        assert_eq!(lisp!((oct 15)), "0o17");
+        assert_eq!(lisp!((hex (* 2 16))), "0x20");
+    }
+
+    #[test]
+    fn string_padding_and_repeat() {
+        assert_eq!(lisp!((str-repeat "ab" 3)), "ababab");
+        assert_eq!(lisp!((str-repeat "-" 0)), "");
+        assert_eq!(lisp!((str-repeat "-" (- 2))), "");
+        assert_eq!(lisp!((pad-left "7" 3)), "  7");
+        assert_eq!(lisp!((pad-right 42 4)), "42  ");
+        assert_eq!(lisp!((pad-left "long" 2)), "long");
+        assert_eq!(lisp!((pad-right "x" (- 5))), "x");
+    }
+
     #[test]
     fn function_definitions_and_calls() {
         // Test function definition and calling with new closure-based approach
@@ -376,6 +988,121 @@ mod tests {
             lisp!((call complex_fn 3 4))
         };
         assert_eq!(result3, 25); // 3² + 4² = 9 + 16 = 25
+
+        // The return type is inferred from the body, so predicates return bool
+        let is_even = lisp!((defn is_even [x] (even x)));
+        assert!(lisp!((call is_even 4)));
+        assert!(!lisp!((call is_even 7)));
+    }
+
+    lisp_item!((defn documented_cube [x] "Cubes its argument." (* x x x)));
+    lisp_item!((defn countdown [n] (if (lte n 0) 0 (countdown (- n 1)))));
+    lisp_item!((defn total "Sums with an optional starting value."
+                ([x] (total x 0))
+                ([x start] (+ x start))
+                ([x y start] (total (+ x y) start))));
+    lisp_item!((def MAX_RETRIES 3));
+    lisp_item!((def TAX_RATE 0.25));
+    lisp_item!((def VERBOSE false));
+    lisp_item!((def GREETING "hello"));
+    lisp_item!((def BIG 5_000_000_000i64));
+    lisp_item!((def AREA (* 4 5)));
+
+    #[test]
+    fn defn_docstrings() {
+        // The docstring is skipped in the closure form
+        let cube = lisp!((defn cube [x] "Cubes its argument." (* x x x)));
+        assert_eq!(lisp!((call cube 3)), 27);
+
+        // lisp_item! turns it into a doc comment on a real function
+        assert_eq!(documented_cube(3), 27);
+        assert_eq!(countdown(5), 0);
+    }
+
+    #[test]
+    fn multi_arity_items() {
+        // Each arity becomes its own function, suffixed with its parameter count
+        assert_eq!(total_1(5), 5);
+        assert_eq!(total_2(5, 10), 15);
+        assert_eq!(total_3(1, 2, 3), 6);
+    }
+
+    #[test]
+    fn item_constants() {
+        assert_eq!(MAX_RETRIES + 1, 4);
+        assert_eq!(TAX_RATE * 100.0, 25.0);
+        const { assert!(!VERBOSE) };
+        assert_eq!(GREETING.len(), 5);
+        assert_eq!(BIG, 5_000_000_000i64);
+        assert_eq!(AREA, 20);
+
+        // Constants are usable from BigLisp code too
+        assert_eq!(lisp!((* MAX_RETRIES AREA)), 60);
+    }
+
+    #[test]
+    fn letfn_mutual_recursion() {
+        let parity = lisp!((letfn [(even? [n] (if (= n 0) 1 (odd? (- n 1))))
+                                   (odd? [n] (if (= n 0) 0 (even? (- n 1))))]
+                             (list (even? 10) (odd? 10) (even? 7))));
+        assert_eq!(parity, vec![1, 0, 0]);
+
+        // `:as` gives the functions a return type other than `i32`
+        let parity = lisp!((letfn [(even? [n] :as bool (if (= n 0) true (odd? (- n 1))))
+                                   (odd? [n] :as bool (if (= n 0) false (even? (- n 1))))]
+                             (list (even? 10) (odd? 10) (even? 7))));
+        assert_eq!(parity, vec![true, false, false]);
+
+        assert_eq!(lisp!((letfn [(fact [n] (if (lte n 1) 1 (* n (fact (- n 1)))))] (fact 5))), 120);
+    }
+
+    #[test]
+    fn identity_and_constantly() {
+        assert_eq!(lisp!((identity 5)), 5);
+        assert_eq!(lisp!((identity (+ 2 3))), 5);
+
+        // constantly ignores its argument, so mapping it fills a vector
+        let filled: Vec<i32> = (0..4).map(lisp!((constantly 7))).collect();
+        assert_eq!(filled, vec![7, 7, 7, 7]);
+
+        let greeting = lisp!((constantly "hi"));
+        assert_eq!(lisp!((call greeting 99)), "hi");
+    }
+
+    #[test]
+    fn predicate_combinators() {
+        let positive_even = lisp!((every-pred pos even));
+        let kept: Vec<i32> = vec![-2, -1, 0, 1, 2, 3, 4]
+            .into_iter()
+            .filter(|&x| positive_even(x))
+            .collect();
+        assert_eq!(kept, vec![2, 4]);
+
+        assert_eq!(lisp!((count-if (any-pred neg even) [(- 3) (- 1) 0 1 2 5])), 4);
+
+        // Function values combine with builtins
+        let small = lisp!((defn small [x] (< x 10)));
+        assert_eq!(lisp!((count-if (every-pred small odd) [1 3 12 15])), 2);
+
+        // A computed predicate is built once, not on every call
+        let built = std::cell::Cell::new(0);
+        let make_small = || {
+            built.set(built.get() + 1);
+            small
+        };
+        let small_or_even = lisp!((any-pred (make_small) even));
+        assert_eq!(lisp!((count-if small_or_even [1 12 15 20])), 3);
+        assert_eq!(built.get(), 1);
+    }
+
+    #[test]
+    fn juxt_operation() {
+        let inc = lisp!((defn inc [x] (+ x 1)));
+        let dec = lisp!((defn dec [x] (- x 1)));
+        assert_eq!(lisp!((call (juxt inc dec) 5)), (6, 4));
+
+        let stats = lisp!((juxt (fn [x] (* x x)) (fn [x] (even x)) inc));
+        assert_eq!(stats(3), (9, false, 4));
     }
 
     #[test]
@@ -385,9 +1112,226 @@ mod tests {
 
         // Test that dotimes executes without panicking
         let _result_sum: () = lisp!((dotimes i 3 (* (+ i 1) 2)));
+    }
+
+    #[test]
+    fn mutating_increments() {
+        let mut count = 0;
+        let _: () = lisp!((while (< count 5) (inc! count)));
+        assert_eq!(count, 5);
+
+        let mut left = 3;
+        let mut steps = 0;
+        lisp!((while (pos left) (do (dec! left) (inc! steps))));
+        assert_eq!((left, steps), (0, 3));
+    }
+
+    #[test]
+    fn atoms() {
+        let counter = lisp!((atom 0));
+        let inc = lisp!((defn inc [x] (+ x 1)));
+        lisp!((swap! counter inc));
+        lisp!((swap! counter inc));
+        lisp!((swap! counter inc));
+        assert_eq!(lisp!((deref counter)), 3);
+
+        let double = lisp!((defn double [x] (* x 2)));
+        let _: () = lisp!((swap! counter double));
+        assert_eq!(lisp!((+ (deref counter) 1)), 7);
+
+        // Reading an atom bound by `let` as the block's last form must not
+        // leave its borrow guard outliving the atom
+        assert_eq!(lisp!((let [total (atom 5)] (deref total))), 5);
+    }
+
+    #[test]
+    fn closure_factory() {
+        let add_ten = lisp!((closure [state 10] [x] (+ x state)));
+        assert_eq!(add_ten(1), 11);
+        assert_eq!(add_ten(5), 15);
+
+        // Bindings can use captured Rust variables
+        let base = 3;
+        let scale = lisp!([base] (closure [factor (* base 2) offset 1] [x] (+ (* x factor) offset)));
+        assert_eq!(scale(2), 13);
+    }
+
+    #[test]
+    fn memoize_function() {
+        use std::cell::Cell;
+
+        let square = lisp!((memoize (defn square [x] (* x x))));
+        assert_eq!(lisp!((call square 4)), 16);
+        assert_eq!(lisp!((call square 4)), 16);
+        assert_eq!(lisp!((call square 5)), 25);
+
+        // The wrapped function only runs once per distinct argument
+        let runs = Cell::new(0);
+        let slow = |x: i32| {
+            runs.set(runs.get() + 1);
+            x + 100
+        };
+        let fast = lisp!((memoize slow));
+        assert_eq!(lisp!((call fast 1)), 101);
+        assert_eq!(lisp!((call fast 1)), 101);
+        assert_eq!(lisp!((call fast 2)), 102);
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn while_loops() {
+        use std::cell::Cell;
+
+        // A body returning a non-unit value used to fail to compile
+        let counter = Cell::new(0);
+        let current = || counter.get();
+        let bump = || {
+            counter.set(counter.get() + 1);
+            counter.get()
+        };
+        let _done: () = lisp!((while (< (call current) 5) (call bump)));
+        assert_eq!(counter.get(), 5);
+
+        // while-collect keeps each iteration's value
+        counter.set(0);
+        let collected = lisp!((while-collect (< (call current) 3) (* (call bump) 10)));
+        assert_eq!(collected, vec![10, 20, 30]);
+    }
+
+    #[test]
+    fn dotimes_sum_accumulation() {
+        // 0 + 1 + ... + 9
+        assert_eq!(lisp!((dotimes-sum i 10 i)), 45);
+
+        // Sum of squares 0² + 1² + 2² + 3²
+        assert_eq!(lisp!((dotimes-sum i 4 (* i i))), 14);
+    }
+
+    #[test]
+    fn doseq_iteration() {
+        // doseq runs the body once per element and returns unit
+        let _printed: () = lisp!((doseq [x [1 2 3]] (println x)));
+
+        let data = vec![4, 5, 6];
+        let _from_capture: () = lisp!([data] (doseq [n data] (println (* n 2))));
+    }
+
+    #[test]
+    fn doseq_indexed_iteration() {
+        let _printed: () = lisp!((doseq-indexed [i x [10 20 30]] (println (str i ": " x))));
+
+        // Each element is paired with its index: 0*10 + 1*20 + 2*30
+        let weighted = lisp!(
+            (let [total (atom 0)]
+              (do
+                (doseq-indexed [i x [10 20 30]]
+                  (swap! total (defn add [t] (+ t (* i x)))))
+                (deref total))));
+        assert_eq!(weighted, 80);
+    }
+
+    #[test]
+    fn condp_dispatch() {
+        let x = 2;
+        assert_eq!(lisp!((condp = x 1 "one" 2 "two" "other")), "two");
+        assert_eq!(lisp!((condp = (+ x 5) 1 "one" 2 "two" "other")), "other");
+
+        // Any two-argument function works as the predicate
+        let divides = |n: i32, d: i32| n % d == 0;
+        assert_eq!(lisp!((condp divides 9 2 "even" 3 "by three" "neither")), "by three");
+        assert_eq!(lisp!((condp > 7 10 "big" 5 "medium" "small")), "medium");
+    }
+
+    #[test]
+    fn case_dispatch() {
+        let x = 2;
+        assert_eq!(lisp!((case x 1 "one" 2 "two" "other")), "two");
+
+        let y = 7;
+        assert_eq!(lisp!((case y 1 "one" 2 "two" "other")), "other");
+
+        // Results can be arbitrary expressions
+        assert_eq!(lisp!((case (+ x 1) 3 (* x 10) 0)), 20);
+    }
+
+    #[test]
+    fn case_on_strings() {
+        let s = "b";
+        assert_eq!(lisp!((case s "a" 1 "b" 2 0)), 2);
+        assert_eq!(lisp!((case "zzz" "a" 1 "b" 2 0)), 0);
+
+        // Owned strings match the same literal arms
+        let owned = String::from("a");
+        assert_eq!(lisp!((case owned "a" 1 "b" 2 0)), 1);
+        assert_eq!(lisp!((case (str "b") "a" 1 "b" 2 0)), 2);
+    }
+
+    #[test]
+    fn conditional_threading() {
+        // (> x 0) holds so x is incremented; (even x) does not, so no doubling
+        let x = 5;
+        assert_eq!(lisp!((cond-> x (> x 0) (inc) (even x) (* 2))), 6);
+
+        // Steps see the threaded value, conditions see the original bindings
+        let y = 4;
+        assert_eq!(lisp!((cond-> y (> y 0) (inc) (even y) (* 2))), 10);
+
+        // Bare symbols are threaded like single-element lists
+        assert_eq!(lisp!((cond-> 1 true inc false dec)), 2);
+    }
+
+    #[test]
+    fn named_threading() {
+        // The value moves between positions from one step to the next
+        assert_eq!(lisp!((as-> 5 v (- v 2) (- 10 v) (* v v))), 49);
 
-        // These tests mainly verify the advanced control flow compiles
-        assert!(true);
+        let subtract = |a: i32, b: i32| a - b;
+        assert_eq!(lisp!((as-> 1 n (subtract n 10) (subtract 100 n))), 109);
+
+        // With no steps the initial value is returned
+        assert_eq!(lisp!((as-> 3 x)), 3);
+    }
+
+    #[test]
+    fn some_threading() {
+        let half = |n: i32| (n % 2 == 0).then_some(n / 2);
+        assert_eq!(lisp!((some-> (Some 20) half half)), Some(5));
+
+        // 5 is odd, so the third step yields None and skips the rest
+        let calls = std::cell::Cell::new(0);
+        let counted = |n: i32| {
+            calls.set(calls.get() + 1);
+            Some(n)
+        };
+        assert_eq!(lisp!((some-> (Some 20) half half half counted)), None);
+        assert_eq!(calls.get(), 0);
+
+        let checked_sub = |a: i32, b: i32| a.checked_sub(b);
+        assert_eq!(lisp!((some-> (Some 10) (checked_sub 3) half)), None);
+        assert_eq!(lisp!((some-> (Some 10) (checked_sub 4) half)), Some(3));
+        assert_eq!(lisp!((some-> None half)), None);
+    }
+
+    #[test]
+    fn doto_threading() {
+        let inc = lisp!((defn inc [x] (+ x 1)));
+        let double = lisp!((defn double [x] (* x 2)));
+        let counter = lisp!((doto (atom 1) (swap! inc) (swap! double)));
+        assert_eq!(counter.into_inner(), 4);
+
+        assert_eq!(lisp!((doto 7)), 7);
+    }
+
+    #[test]
+    fn for_comprehension() {
+        // Squares of the even numbers in a range
+        let range: Vec<i32> = (0..10).collect();
+        let even_squares = lisp!([range] (for [x range :when (even x)] (* x x)));
+        assert_eq!(even_squares, vec![0, 4, 16, 36, 64]);
+
+        // Without a guard every element is mapped
+        let doubled = lisp!((for [x [1 2 3]] (* x 2)));
+        assert_eq!(doubled, vec![2, 4, 6]);
     }
 
     #[test]
@@ -405,6 +1349,87 @@ mod tests {
         assert_eq!(nested_try, 10);
     }
 
+    #[test]
+    fn internal_temporaries_do_not_shadow_user_bindings() {
+        // `try` used to bind its own `result`, hiding this one in the fallback
+        let result = 42;
+        let fail = || -> i32 { panic!("expected failure") };
+        assert_eq!(lisp!((try (call fail) result)), 42);
+
+        // `while-collect` used to accumulate into a `results` vector
+        let results = std::cell::Cell::new(0);
+        let next = || {
+            results.set(results.get() + 1);
+            results.get()
+        };
+        let collected = lisp!((while-collect (< (call next) 3) (* 2 (call next))));
+        assert_eq!(collected, vec![4]);
+
+        // Conditions and steps see the user's `v` and `start`, not the form's
+        let v = 10;
+        assert_eq!(lisp!((cond-> 1 (> v 5) (+ v))), 11);
+        let start = 7;
+        assert_eq!(lisp!((time (+ start 1))), 8);
+        let f = 3;
+        let key = |x: i32| x;
+        assert_eq!(lisp!((max-by key (list f 1))), Some(3));
+    }
+
+    #[test]
+    fn try_fallback_is_silent() {
+        // The panic message is suppressed and the fallback value returned
+        let fail = || -> i32 { panic!("should not be printed") };
+        assert_eq!(lisp!((try (call fail) 7)), 7);
+
+        // try-verbose reports the panic but still falls back
+        assert_eq!(lisp!((try-verbose (call fail) 8)), 8);
+        assert_eq!(lisp!((try-verbose (+ 1 2) 0)), 3);
+
+        // Without a fallback the panic still propagates
+        let caught = std::panic::catch_unwind(|| lisp!((try (call fail))));
+        assert!(caught.is_err());
+
+        // A `try` in a `finally` cleanup can run while the thread unwinds
+        let caught = std::panic::catch_unwind(|| lisp!((try (call fail) (finally (try (+ 1 2) 0)))));
+        assert!(caught.is_err());
+    }
+
+    #[test]
+    fn try_finally_always_runs() {
+        use std::cell::Cell;
+
+        let cleaned = Cell::new(false);
+        let mark = || cleaned.set(true);
+
+        // Success path
+        assert_eq!(lisp!((try (+ 1 2) (catch 0) (finally (call mark)))), 3);
+        assert!(cleaned.get());
+
+        // Fallback path, with the clauses in the other order
+        cleaned.set(false);
+        assert_eq!(lisp!((try (throw "boom") (finally (call mark)) (catch 99))), 99);
+        assert!(cleaned.get());
+
+        // A bare fallback can still be combined with finally
+        cleaned.set(false);
+        assert_eq!(lisp!((try (throw "boom") 7 (finally (call mark)))), 7);
+        assert!(cleaned.get());
+    }
+
+    #[test]
+    fn throw_and_catch() {
+        assert_eq!(lisp!((try (throw "boom") 99)), 99);
+
+        // throw fits in any branch
+        let x = 5;
+        assert_eq!(lisp!((if (> x 0) (* x 2) (throw "negative"))), 10);
+        let message = String::from("bad input");
+        assert_eq!(lisp!((try (if (< x 0) 1 (throw message)) 0)), 0);
+
+        let thrown = std::panic::catch_unwind(|| -> i32 { lisp!((throw "uncaught")) });
+        assert!(thrown.is_err());
+    }
+
     #[test]
     fn complex_combinations() {
         // Test combining multiple advanced features
@@ -425,7 +1450,7 @@ mod tests {
 
         // Test boolean logic with list operations
         let vec_test = lisp!((and (> (count [1 2 3 4]) 2) (= (first [5 6 7]) 5)));
-        assert_eq!(vec_test, true);
+        assert!(vec_test);
     }
 
     #[test]
@@ -482,20 +1507,24 @@ mod tests {
         assert_eq!(lisp!((/ 24 2 3)), 4);
 
         // ✅ 2. Comparison Operations (including new ones)
-        assert_eq!(lisp!((= 5 5)), true);
-        assert_eq!(lisp!((< 3 7)), true);
-        assert_eq!(lisp!((> 10 5)), true);
-        assert_eq!(lisp!((lte 5 5)), true);
-        assert_eq!(lisp!((lte 3 7)), true);
-        assert_eq!(lisp!((gte 5 5)), true);
-        assert_eq!(lisp!((gte 7 3)), true);
-        assert_eq!(lisp!((ne 3 7)), true);
-        assert_eq!(lisp!((ne 5 5)), false);
+        const {
+            assert!(lisp!((= 5 5)));
+            assert!(lisp!((< 3 7)));
+            assert!(lisp!((> 10 5)));
+            assert!(lisp!((lte 5 5)));
+            assert!(lisp!((lte 3 7)));
+            assert!(lisp!((gte 5 5)));
+            assert!(lisp!((gte 7 3)));
+            assert!(lisp!((ne 3 7)));
+            assert!(!lisp!((ne 5 5)));
+        }
 
         // ✅ 3. Boolean Logic
-        assert_eq!(lisp!((and true true false)), false);
-        assert_eq!(lisp!((or false true)), true);
-        assert_eq!(lisp!((not false)), true);
+        const {
+            assert!(!lisp!((and true true false)));
+            assert!(lisp!((or false true)));
+            assert!(lisp!((not false)));
+        }
 
         // ✅ 4. Conditionals
         assert_eq!(lisp!((if (> 5 3) 100 200)), 100);
@@ -557,8 +1586,10 @@ mod tests {
         assert_eq!(lisp!((*7)), 7); // Single multiplication
 
         // ✅ 16. Boolean combinations with comparisons
-        assert_eq!(lisp!((and (> 5 3) (< 2 10) (= 4 4))), true);
-        assert_eq!(lisp!((or (< 5 3) (> 2 10) (= 4 5))), false);
+        const {
+            assert!(lisp!((and (> 5 3) (< 2 10) (= 4 4))));
+            assert!(!lisp!((or (< 5 3) (> 2 10) (= 4 5))));
+        }
 
         // ✅ 17. Simple function without variable capture (to avoid closure lifetime issues)
         let simple_func = lisp!((defn add_hundred [x] (+ x 100)));
@@ -570,16 +1601,54 @@ mod tests {
     #[test]
     fn new_comparison_operators() {
         // Test new comparison operators
-        assert_eq!(lisp!((gte 10 5)), true);
-        assert_eq!(lisp!((gte 5 5)), true);
-        assert_eq!(lisp!((gte 3 7)), false);
+        const {
+            assert!(lisp!((gte 10 5)));
+            assert!(lisp!((gte 5 5)));
+            assert!(!lisp!((gte 3 7)));
+        }
+
+        const {
+            assert!(lisp!((lte 3 7)));
+            assert!(lisp!((lte 5 5)));
+            assert!(!lisp!((lte 10 5)));
+        }
+
+        const {
+            assert!(lisp!((ne 5 3)));
+            assert!(!lisp!((ne 5 5)));
+        }
+    }
 
-        assert_eq!(lisp!((lte 3 7)), true);
-        assert_eq!(lisp!((lte 5 5)), true);
-        assert_eq!(lisp!((lte 10 5)), false);
+    #[test]
+    fn clojure_style_predicates() {
+        const {
+            assert!(lisp!((not= 3 4)));
+            assert!(!lisp!((not= 4 4)));
+        }
+
+        let empty: Option<Vec<i32>> = lisp!((not-empty []));
+        assert_eq!(empty, None);
+        assert_eq!(lisp!((not-empty [1 2])), Some(vec![1, 2]));
+    }
 
-        assert_eq!(lisp!((ne 5 3)), true);
-        assert_eq!(lisp!((ne 5 5)), false);
+    #[test]
+    fn range_predicates() {
+        assert!(lisp!((between? 5 1 10)));
+        assert!(lisp!((between? 10 1 10)));
+        assert!(!lisp!((between? 11 1 10)));
+        assert!(lisp!((in-range? 0 0 10)));
+        assert!(!lisp!((in-range? 10 0 10)));
+    }
+
+    #[test]
+    fn truthiness() {
+        const {
+            assert!(!lisp!((truthy? 0)));
+            assert!(lisp!((truthy? 3)));
+            assert!(lisp!((truthy? (- 2))));
+            assert!(lisp!((falsy? (- 2 2))));
+            assert!(!lisp!((falsy? 1i64)));
+        }
     }
 
     #[test]
@@ -597,6 +1666,37 @@ mod tests {
         // Test absolute value
         assert_eq!(lisp!((abs 5)), 5);
         assert_eq!(lisp!((abs (- 0 7))), 7);
+
+        // Floats and wider integers are no longer truncated to i32
+        assert_eq!(lisp!((abs (- 7.5))), 7.5);
+        let big: i64 = -5_000_000_000;
+        assert_eq!(lisp!((abs big)), 5_000_000_000);
+    }
+
+    #[test]
+    fn boolean_counting() {
+        assert_eq!(lisp!((bool->int true)), 1);
+        assert_eq!(lisp!((bool->int false)), 0);
+        assert_eq!(lisp!((count-true [true false true])), 2);
+        assert_eq!(lisp!((count-if (defn ev [x] (even x)) [1 2 3 4 5 6])), 3);
+
+        // Predicate results can be summed into a score
+        let x = 4;
+        assert_eq!(lisp!((+ (bool->int (even x)) (bool->int (pos x)))), 2);
+    }
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(lisp!((gcd 12 18)), 6);
+        assert_eq!(lisp!((gcd 12 18 24)), 6);
+        assert_eq!(lisp!((lcm 4 6)), 12);
+        assert_eq!(lisp!((lcm 2 3 4)), 12);
+
+        // Unsigned and negative operands
+        assert_eq!(lisp!((gcd 12u32 18u32)), 6u32);
+        assert_eq!(lisp!((lcm 4u64 6u64)), 12u64);
+        assert_eq!(lisp!((gcd (- 12) 18)), 6);
+        assert_eq!(lisp!((lcm (- 4) 6)), 12);
     }
 
     #[test]
@@ -606,31 +1706,41 @@ mod tests {
         assert_eq!(lisp!((dec 10)), 9);
 
         // Test predicates
-        assert_eq!(lisp!((zero 0)), true);
-        assert_eq!(lisp!((zero 5)), false);
-
-        assert_eq!(lisp!((pos 5)), true);
-        assert_eq!(lisp!((pos 0)), false);
-        assert_eq!(lisp!((pos (- 0 3))), false);
-
-        assert_eq!(lisp!((neg (- 0 5))), true);
-        assert_eq!(lisp!((neg 0)), false);
-        assert_eq!(lisp!((neg 3)), false);
-
-        assert_eq!(lisp!((even 4)), true);
-        assert_eq!(lisp!((even 5)), false);
-        assert_eq!(lisp!((even 0)), true);
-
-        assert_eq!(lisp!((odd 3)), true);
-        assert_eq!(lisp!((odd 4)), false);
-        assert_eq!(lisp!((odd 1)), true);
+        const {
+            assert!(lisp!((zero 0)));
+            assert!(!lisp!((zero 5)));
+        }
+
+        const {
+            assert!(lisp!((pos 5)));
+            assert!(!lisp!((pos 0)));
+            assert!(!lisp!((pos (- 0 3))));
+        }
+
+        const {
+            assert!(lisp!((neg (- 0 5))));
+            assert!(!lisp!((neg 0)));
+            assert!(!lisp!((neg 3)));
+        }
+
+        const {
+            assert!(lisp!((even 4)));
+            assert!(!lisp!((even 5)));
+            assert!(lisp!((even 0)));
+        }
+
+        const {
+            assert!(lisp!((odd 3)));
+            assert!(!lisp!((odd 4)));
+            assert!(lisp!((odd 1)));
+        }
     }
 
     #[test]
     fn complex_expressions_with_new_operators() {
         // Test complex expressions using new operators
         let result1 = lisp!((and (gte 10 5) (lte 3 7) (ne 4 5)));
-        assert_eq!(result1, true);
+        assert!(result1);
 
         let result2 = lisp!((max (min 10 5) (abs (- 0 3))));
         assert_eq!(result2, 5);
@@ -642,7 +1752,7 @@ mod tests {
         let x = 15;
         let y = 4;
         let result4 = lisp!([x, y] (and (gte x 10) (zero (% x y))));
-        assert_eq!(result4, false); // 15 >= 10 is true, but 15 % 4 != 0
+        assert!(!result4); // 15 >= 10 is true, but 15 % 4 != 0
     }
 
     // Note: For complex macro calls that formatters keep breaking, you can use:
@@ -90,8 +90,8 @@ fn main() {
     println!("  (if (> 5 3) \"yes\" \"no\") = {}", cond1);
     println!("  (if (= 2 3) 100 200) = {}", cond2);
     println!(
-        "  Age {}: {} = {}",
-        user_age, "(if (> user_age 18) \"adult\" \"minor\")", adult_check
+        "  Age {}: (if (> user_age 18) \"adult\" \"minor\") = {}",
+        user_age, adult_check
     );
     println!();
 
@@ -237,17 +237,21 @@ mod tests {
 
     #[test]
     fn test_comparisons() {
-        assert_eq!(lisp!((= 5 5)), true);
-        assert_eq!(lisp!((< 3 7)), true);
-        assert_eq!(lisp!((> 10 5)), true);
+        const {
+            assert!(lisp!((= 5 5)));
+            assert!(lisp!((< 3 7)));
+            assert!(lisp!((> 10 5)));
+        }
     }
 
     #[test]
     fn test_boolean_logic() {
-        assert_eq!(lisp!((and true true)), true);
-        assert_eq!(lisp!((and true false)), false);
-        assert_eq!(lisp!((or false true)), true);
-        assert_eq!(lisp!((not false)), true);
+        const {
+            assert!(lisp!((and true true)));
+            assert!(!lisp!((and true false)));
+            assert!(lisp!((or false true)));
+            assert!(lisp!((not false)));
+        }
     }
 
     #[test]